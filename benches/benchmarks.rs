@@ -56,5 +56,33 @@ pub fn schematic_fill(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, schematic_merge, schematic_fill);
+pub fn schematic_to_bytes(c: &mut Criterion) {
+    let schematic_sizes: Vec<u16> = (5..=7).map(|pow| 2_u16.pow(pow)).collect();
+
+    let mut group = c.benchmark_group("Schematic::to_bytes");
+    group.sample_size(10);
+
+    for schematic_size in schematic_sizes {
+        let mut schematic =
+            Schematic::new(MapVector::new(schematic_size, schematic_size, schematic_size).unwrap())
+                .unwrap();
+        // Mostly air, with a solid floor
+        schematic
+            .fill(
+                MapVector::new(0, 0, 0).unwrap(),
+                MapVector::new(schematic_size, 1, schematic_size).unwrap(),
+                &Node::with_content_name("default:cobble".into()),
+            )
+            .unwrap();
+
+        group.throughput(criterion::Throughput::Elements(schematic.num_nodes() as u64));
+        group.bench_function(BenchmarkId::from_parameter(schematic_size), |b| {
+            b.iter(|| schematic.to_bytes())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, schematic_merge, schematic_fill, schematic_to_bytes);
 criterion_main!(benches);
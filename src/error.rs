@@ -1,5 +1,13 @@
+use alloc::string::String;
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
+    #[error("Could not decompress the node data: {0}")]
+    DecompressionFailed(String),
+    #[error("Content name is listed more than once: {0}")]
+    DuplicateContentName(String),
+    #[error("Schematic dimensions must be at least 1 node in every direction")]
+    EmptyDimension,
     #[error("Schematic has too many or too few nodes: {found} instead of {expected}")]
     IncorrectNodeCount { found: usize, expected: usize },
     #[error("Number of layer probabilities does not match number of layers")]
@@ -8,10 +16,16 @@ pub enum Error {
     InvalidContentIndex(u16),
     #[error("Unregistered content name: {0}")]
     InvalidContentName(String),
+    #[error("Weights must be positive and finite, and there must be at least one")]
+    InvalidWeights,
     #[error("Out of bounds")]
     OutOfBounds,
     #[error("Parse error: {0}")]
     ParseError(winnow::error::ContextError),
+    #[error("Schematic has {volume} nodes, which is more than the maximum of {max_nodes}")]
+    TooLarge { volume: usize, max_nodes: usize },
+    #[error("Too large to store in a schematic pack: {0}")]
+    TooLargeForPack(String),
 }
 
 impl From<winnow::error::ContextError> for Error {
@@ -1,9 +1,24 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 mod error;
 mod node;
+#[cfg(feature = "std")]
+mod pack;
+mod param2;
+#[cfg(feature = "std")]
 mod schematic;
 mod vector;
 
 pub use error::Error;
 pub use node::{Node, NodeSpace, RawNode, SpawnProbability};
-pub use schematic::{Schematic, SchematicRef};
+#[cfg(feature = "std")]
+pub use pack::SchematicPack;
+pub use param2::Facedir;
+#[cfg(feature = "std")]
+pub use schematic::{
+    Axis3, AxisOrder, ChangeRecorder, Lint, NodeChange, Schematic, SchematicRef, SchematicSummary,
+    SymmetryReport, common_content,
+};
 pub use vector::MapVector;
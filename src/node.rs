@@ -1,8 +1,9 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
 
 use ndarray::ArrayView3;
 
 use crate::error::Error;
+use crate::param2::Facedir;
 use crate::vector::MapVector;
 
 /// Trait for interacting with a 3D space of nodes.
@@ -70,6 +71,12 @@ impl<'name> Node<'name> {
         Node::new(content_name, SpawnProbability::Always, true, 0)
     }
 
+    /// Interprets the `param2` of this `Node` as a [Facedir]. Whether that's correct depends on the
+    /// kind of node, which is not known by this crate.
+    pub fn facedir(&self) -> Option<Facedir> {
+        Facedir::from_param2(self.param2)
+    }
+
     /// Converts this `Node` into a `RawNode`.
     ///
     /// This can fail if the `Node`'s content name cannot be found in the `schematic`.
@@ -147,13 +154,33 @@ impl RawNode {
         self.content_id
     }
 
+    /// Returns the node as it is encoded in MTS files: `(content_id, param1, param2)`, where
+    /// `param1` holds `force_placement` in its highest bit and the spawn probability in the other
+    /// 7 bits. Useful for passing node data to other runtimes without going through [Node].
+    pub fn to_packed(&self) -> (u16, u8, u8) {
+        let param1 = u8::from(self.force_placement) << 7 | (self.spawn_probability & 0x7f);
+
+        (self.content_id, param1, self.param2)
+    }
+
+    /// Creates a `RawNode` from the encoding used in MTS files, as returned by
+    /// [to_packed()](RawNode::to_packed).
+    pub fn from_packed(content_id: u16, param1: u8, param2: u8) -> Self {
+        RawNode {
+            content_id,
+            spawn_probability: param1 & 0x7f,
+            force_placement: param1 & 0x80 > 0,
+            param2,
+        }
+    }
+
     pub fn to_node<'schematic>(
         &'schematic self,
         schematic: &'schematic impl NodeSpace<'schematic>,
     ) -> Result<Node<'schematic>, Error> {
         let content_name = schematic
             .content_name_for_id(self.content_id)
-            .ok_or_else(|| Error::InvalidContentIndex(self.content_id))?;
+            .ok_or(Error::InvalidContentIndex(self.content_id))?;
 
         Ok(Node::new(
             content_name.into(),
@@ -166,6 +193,7 @@ impl RawNode {
 
 /// Used by [AnnotatedNodeIterator], combines a [Node] with its `coordinates` inside the
 /// [Schematic]
+#[cfg(feature = "std")]
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct AnnotatedNode<'node> {
     pub coordinates: MapVector,
@@ -178,9 +206,66 @@ pub enum SpawnProbability {
     Never,
     #[default]
     Always,
+    /// A probability from 1 to 126 out of 127. Prefer [custom()](SpawnProbability::custom) to
+    /// create one, as 0 and 127 (or more) are the same as `Never` and `Always`.
     Custom(u8),
 }
 
+impl SpawnProbability {
+    /// Creates a probability from `value` out of 127, normalizing 0 to `Never` and 127 or more to
+    /// `Always`, so that equal probabilities always compare as equal.
+    pub fn custom(value: u8) -> SpawnProbability {
+        SpawnProbability::from(value)
+    }
+
+    /// Interprets `value` as a probability in the legacy format, which used the full range of a
+    /// byte: from 0 (never) to 255 (always). Newer schematics use 0 to 127 instead, as handled by
+    /// the `From<u8>` implementation.
+    pub fn from_legacy(value: u8) -> SpawnProbability {
+        match value {
+            0 => SpawnProbability::Never,
+            // Like Luanti does when loading old schematics, halve the value, but don't let low
+            // probabilities round down to "never"
+            v => SpawnProbability::from((v >> 1).max(1)),
+        }
+    }
+
+    /// Converts the probability into the legacy format, from 0 (never) to 255 (always). See
+    /// [from_legacy()](SpawnProbability::from_legacy).
+    pub fn to_legacy(&self) -> u8 {
+        match self {
+            SpawnProbability::Never => 0,
+            SpawnProbability::Always => 255,
+            SpawnProbability::Custom(v) => v.saturating_mul(2),
+        }
+    }
+
+    /// Creates a probability from a percentage, where 0 means never and 100 (or more) means
+    /// always. Anything in between is scaled to the 0 to 127 range of schematic files, but never
+    /// ends up as "never" or "always".
+    pub fn from_percent(percent: u8) -> SpawnProbability {
+        match percent {
+            0 => SpawnProbability::Never,
+            100.. => SpawnProbability::Always,
+            p => {
+                let scaled = (u16::from(p) * 127 + 50) / 100;
+
+                SpawnProbability::Custom(scaled.clamp(1, 126) as u8)
+            }
+        }
+    }
+
+    /// Converts the probability to a percentage, from 0 (never) to 100 (always). See
+    /// [from_percent()](SpawnProbability::from_percent).
+    pub fn to_percent(&self) -> u8 {
+        match self {
+            SpawnProbability::Never => 0,
+            SpawnProbability::Always => 100,
+            SpawnProbability::Custom(v) => ((u16::from((*v).min(127)) * 100 + 63) / 127) as u8,
+        }
+    }
+}
+
 impl From<u8> for SpawnProbability {
     fn from(value: u8) -> Self {
         match value {
@@ -196,7 +281,7 @@ impl From<u8> for SpawnProbability {
 impl From<SpawnProbability> for u8 {
     fn from(value: SpawnProbability) -> Self {
         match value {
-            SpawnProbability::Never => 9,
+            SpawnProbability::Never => 0,
             SpawnProbability::Always => 127,
             SpawnProbability::Custom(v) => v,
         }
@@ -211,12 +296,13 @@ impl From<&SpawnProbability> for u8 {
 
 #[cfg(test)]
 mod tests {
-    use crate::Schematic;
-
     use super::*;
 
+    #[cfg(feature = "std")]
     #[test]
     fn test_node_to_raw_node() {
+        use crate::Schematic;
+
         let mut schematic = Schematic::with_raw_nodes(
             (1, 1, 1).try_into().unwrap(),
             vec![RawNode::new(0, SpawnProbability::Always, true, 0)],
@@ -236,6 +322,83 @@ mod tests {
         assert_eq!(raw_node.content_id, 1);
     }
 
+    #[test]
+    fn test_packed_round_trip() {
+        let raw_node = RawNode::new(300, SpawnProbability::Custom(50), true, 7);
+
+        let packed = raw_node.to_packed();
+        assert_eq!(packed, (300, 0x80 | 50, 7));
+        assert_eq!(RawNode::from_packed(packed.0, packed.1, packed.2), raw_node);
+
+        let raw_node = RawNode::new(2, SpawnProbability::Always, false, 0);
+        let (content_id, param1, param2) = raw_node.to_packed();
+        assert_eq!(param1, 127);
+        assert_eq!(RawNode::from_packed(content_id, param1, param2), raw_node);
+    }
+
+    #[test]
+    fn test_legacy_probability() {
+        assert_eq!(SpawnProbability::from_legacy(255), SpawnProbability::Always);
+        assert_eq!(SpawnProbability::from_legacy(254), SpawnProbability::Always);
+        assert_eq!(SpawnProbability::from_legacy(0), SpawnProbability::Never);
+        assert_eq!(
+            SpawnProbability::from_legacy(1),
+            SpawnProbability::Custom(1)
+        );
+        assert_eq!(
+            SpawnProbability::from_legacy(128),
+            SpawnProbability::Custom(64)
+        );
+
+        assert_eq!(SpawnProbability::Always.to_legacy(), 255);
+        assert_eq!(SpawnProbability::Never.to_legacy(), 0);
+        assert_eq!(SpawnProbability::Custom(64).to_legacy(), 128);
+        assert_eq!(
+            SpawnProbability::from_legacy(SpawnProbability::Custom(50).to_legacy()),
+            SpawnProbability::Custom(50)
+        );
+    }
+
+    #[test]
+    fn test_custom_probability() {
+        assert_eq!(SpawnProbability::custom(0), SpawnProbability::Never);
+        assert_eq!(SpawnProbability::custom(127), SpawnProbability::Always);
+        assert_eq!(SpawnProbability::custom(200), SpawnProbability::Always);
+        assert_eq!(SpawnProbability::custom(50), SpawnProbability::Custom(50));
+    }
+
+    #[test]
+    fn test_percent_probability() {
+        assert_eq!(SpawnProbability::from_percent(0), SpawnProbability::Never);
+        assert_eq!(
+            SpawnProbability::from_percent(100),
+            SpawnProbability::Always
+        );
+        assert_eq!(
+            SpawnProbability::from_percent(50),
+            SpawnProbability::Custom(64)
+        );
+        assert_eq!(
+            SpawnProbability::from_percent(1),
+            SpawnProbability::Custom(1)
+        );
+        assert_eq!(
+            SpawnProbability::from_percent(99),
+            SpawnProbability::Custom(126)
+        );
+
+        assert_eq!(SpawnProbability::Never.to_percent(), 0);
+        assert_eq!(SpawnProbability::Always.to_percent(), 100);
+        for percent in 0..=100 {
+            let round_trip = SpawnProbability::from_percent(percent).to_percent();
+
+            assert!(
+                round_trip.abs_diff(percent) <= 1,
+                "{percent}% became {round_trip}%"
+            );
+        }
+    }
+
     #[test]
     fn test_send() {
         fn assert_send<T: Send>() {}
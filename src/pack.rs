@@ -0,0 +1,222 @@
+//! A container for storing multiple related schematics in a single file, e.g. for map packs.
+//!
+//! This is a format defined by this crate, Luanti can't load it. The format is:
+//!
+//! * magic bytes "MTSP"
+//! * version (u16, currently 1)
+//! * number of schematics (u16)
+//! * the index, for each schematic: the length of its name (u16), the name, and the length of its
+//!   MTS data (u32)
+//! * the MTS data of all schematics, in the same order as the index
+//!
+//! All numbers are stored as big-endian, like in MTS files.
+
+use std::collections::BTreeMap;
+
+use winnow::BStr;
+use winnow::Parser;
+use winnow::binary::{be_u16, be_u32, length_repeat, length_take};
+use winnow::combinator::eof;
+use winnow::token::{literal, take};
+
+use crate::error::Error;
+use crate::schematic::{Schematic, parser_expected};
+
+const PACK_MAGIC_BYTES: &[u8; 4] = b"MTSP";
+const PACK_VERSION: u16 = 1;
+
+/// A collection of named [Schematic]s that can be stored in a single file.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SchematicPack {
+    schematics: BTreeMap<String, Schematic>,
+}
+
+impl SchematicPack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the `schematic` to the pack under the given `name`, replacing any schematic that
+    /// already had that name.
+    pub fn add(&mut self, name: String, schematic: Schematic) {
+        self.schematics.insert(name, schematic);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Schematic> {
+        self.schematics.get(name)
+    }
+
+    /// Iterator for the names of the schematics in the pack, in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.schematics.keys().map(String::as_str)
+    }
+
+    pub fn from_bytes<T: AsRef<[u8]>>(input: T) -> Result<SchematicPack, Error> {
+        let stream = &mut BStr::new(input.as_ref());
+
+        verify_magic_bytes(stream)?;
+        parse_version(stream)?;
+        let index = parse_index(stream)?;
+
+        let mut pack = SchematicPack::new();
+        for (name, size) in index {
+            let data = parse_schematic_data(stream, size)?;
+
+            pack.add(name, Schematic::from_bytes(data)?);
+        }
+        verify_end(stream)?;
+
+        Ok(pack)
+    }
+
+    /// Converts the pack into bytes, with each schematic stored like
+    /// [Schematic::to_bytes()](Schematic::to_bytes) does.
+    ///
+    /// Returns a [TooLargeForPack](Error::TooLargeForPack) when the pack contains more than 65535
+    /// schematics, a name is longer than 65535 bytes, or a schematic takes up more than 4 GiB.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let num_schematics = u16::try_from(self.schematics.len())
+            .map_err(|_| Error::TooLargeForPack("more than 65535 schematics".to_string()))?;
+        let blobs: Vec<Vec<u8>> = self.schematics.values().map(Schematic::to_bytes).collect();
+
+        let mut output = Vec::new();
+        output.extend(PACK_MAGIC_BYTES);
+        output.extend(PACK_VERSION.to_be_bytes());
+        output.extend(num_schematics.to_be_bytes());
+
+        for (name, blob) in self.schematics.keys().zip(&blobs) {
+            let name_length = u16::try_from(name.len())
+                .map_err(|_| Error::TooLargeForPack(format!("the name of \"{name}\"")))?;
+            let blob_length = u32::try_from(blob.len())
+                .map_err(|_| Error::TooLargeForPack(format!("the schematic \"{name}\"")))?;
+
+            output.extend(name_length.to_be_bytes());
+            output.extend(name.as_bytes());
+            output.extend(blob_length.to_be_bytes());
+        }
+
+        for blob in blobs {
+            output.extend(blob);
+        }
+
+        Ok(output)
+    }
+}
+
+fn verify_magic_bytes(stream: &mut &BStr) -> winnow::Result<()> {
+    literal(PACK_MAGIC_BYTES)
+        .context(parser_expected("magic header bytes to be \"MTSP\""))
+        .parse_next(stream)?;
+
+    Ok(())
+}
+
+fn parse_version(stream: &mut &BStr) -> winnow::Result<u16> {
+    be_u16
+        .verify(|v| *v == PACK_VERSION)
+        .context(parser_expected("version 1"))
+        .parse_next(stream)
+}
+
+/// Parses the names of the schematics in the pack, together with the size of their data.
+fn parse_index(stream: &mut &BStr) -> winnow::Result<Vec<(String, u32)>> {
+    length_repeat(
+        be_u16,
+        (
+            length_take(be_u16)
+                .try_map(|bytes| str::from_utf8(bytes))
+                .map(|name| name.to_string()),
+            be_u32,
+        ),
+    )
+    .context(parser_expected("an index of schematic names and sizes"))
+    .parse_next(stream)
+}
+
+fn parse_schematic_data<'data>(stream: &mut &'data BStr, size: u32) -> winnow::Result<&'data [u8]> {
+    take(size)
+        .context(parser_expected("MTS data for every schematic in the index"))
+        .parse_next(stream)
+}
+
+fn verify_end(stream: &mut &BStr) -> winnow::Result<()> {
+    eof.context(parser_expected("no more data after the last schematic"))
+        .parse_next(stream)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::node::Node;
+
+    #[test]
+    fn test_round_trip() {
+        let data = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/3x3.mts"));
+        let house = Schematic::from_bytes(data).unwrap();
+        let mut tree = Schematic::new((1, 3, 1).try_into().unwrap()).unwrap();
+        tree.fill(
+            (0, 0, 0).try_into().unwrap(),
+            (1, 3, 1).try_into().unwrap(),
+            &Node::with_content_name("default:tree".into()),
+        )
+        .unwrap();
+
+        let mut pack = SchematicPack::new();
+        pack.add("house".to_string(), house.clone());
+        pack.add("tree".to_string(), tree.clone());
+
+        let reloaded_pack = SchematicPack::from_bytes(pack.to_bytes().unwrap()).unwrap();
+
+        assert_eq!(
+            reloaded_pack.names().collect::<Vec<&str>>(),
+            &["house", "tree"]
+        );
+        assert_eq!(reloaded_pack.get("house"), Some(&house));
+        assert_eq!(reloaded_pack.get("tree"), Some(&tree));
+        assert_eq!(reloaded_pack.get("well"), None);
+    }
+
+    #[test]
+    fn test_from_bytes_with_invalid_data() {
+        let mut pack = SchematicPack::new();
+        pack.add(
+            "air".to_string(),
+            Schematic::new((1, 1, 1).try_into().unwrap()).unwrap(),
+        );
+        let mut data = pack.to_bytes().unwrap();
+        data.truncate(data.len() - 1);
+
+        SchematicPack::from_bytes(data).unwrap_err();
+    }
+
+    #[test]
+    fn test_from_bytes_with_trailing_data() {
+        let mut pack = SchematicPack::new();
+        pack.add(
+            "air".to_string(),
+            Schematic::new((1, 1, 1).try_into().unwrap()).unwrap(),
+        );
+        let mut data = pack.to_bytes().unwrap();
+        data.push(0);
+
+        assert!(matches!(
+            SchematicPack::from_bytes(data),
+            Err(Error::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_to_bytes_with_too_long_name() {
+        let mut pack = SchematicPack::new();
+        pack.add(
+            "a".repeat(65536),
+            Schematic::new((1, 1, 1).try_into().unwrap()).unwrap(),
+        );
+
+        assert!(matches!(pack.to_bytes(), Err(Error::TooLargeForPack(_))));
+    }
+}
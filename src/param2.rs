@@ -0,0 +1,124 @@
+//! Typed interpretations of a node's param2 value, which means different things for different
+//! kinds of nodes.
+
+/// The rotation of a node whose param2 is used as "facedir", like for stairs, chests and furnaces.
+///
+/// A facedir consists of the direction the top of the node is facing (`value / 4`), and the
+/// rotation around that direction (`value % 4`), making 24 possible values.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Facedir(u8);
+
+/// For each facedir, what it becomes after rotating it 0, 90, 180 and 270 degrees
+/// counterclockwise around the Y-axis (as seen from above). Taken from Luanti's
+/// `MapNode::rotateAlongYAxis()`.
+const ROTATE_FACEDIR_Y: [[u8; 4]; 24] = [
+    [0, 1, 2, 3],
+    [1, 2, 3, 0],
+    [2, 3, 0, 1],
+    [3, 0, 1, 2],
+    [4, 13, 10, 19],
+    [5, 14, 11, 16],
+    [6, 15, 8, 17],
+    [7, 12, 9, 18],
+    [8, 17, 6, 15],
+    [9, 18, 7, 12],
+    [10, 19, 4, 13],
+    [11, 16, 5, 14],
+    [12, 9, 18, 7],
+    [13, 10, 19, 4],
+    [14, 11, 16, 5],
+    [15, 8, 17, 6],
+    [16, 5, 14, 11],
+    [17, 6, 15, 8],
+    [18, 7, 12, 9],
+    [19, 4, 13, 10],
+    [20, 23, 22, 21],
+    [21, 20, 23, 22],
+    [22, 21, 20, 23],
+    [23, 22, 21, 20],
+];
+
+impl Facedir {
+    /// Interprets `param2` as a facedir. Only the lowest 5 bits are used, the upper bits are used
+    /// by some nodes for other purposes, like their color.
+    ///
+    /// Returns `None` when the value is not a valid facedir.
+    pub fn from_param2(param2: u8) -> Option<Facedir> {
+        let value = param2 & 0b1_1111;
+
+        (value < 24).then_some(Facedir(value))
+    }
+
+    pub fn to_param2(self) -> u8 {
+        self.0
+    }
+
+    /// Rotates the facedir 90 degrees to the left (counterclockwise, as seen from above) around
+    /// the Y-axis.
+    pub fn rotate_y_left(self) -> Facedir {
+        self.rotate_y(1)
+    }
+
+    /// Rotates the facedir 90 degrees to the right (clockwise, as seen from above) around the
+    /// Y-axis.
+    pub fn rotate_y_right(self) -> Facedir {
+        self.rotate_y(3)
+    }
+
+    /// Rotates the facedir 180 degrees around the Y-axis.
+    pub fn rotate_y_180(self) -> Facedir {
+        self.rotate_y(2)
+    }
+
+    fn rotate_y(self, quarter_turns: usize) -> Facedir {
+        Facedir(ROTATE_FACEDIR_Y[self.0 as usize][quarter_turns])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::node::Node;
+
+    #[test]
+    fn test_from_param2() {
+        assert_eq!(Facedir::from_param2(3), Some(Facedir(3)));
+        // Upper bits are ignored
+        assert_eq!(Facedir::from_param2(0b1110_0011), Some(Facedir(3)));
+        assert_eq!(Facedir::from_param2(24), None);
+        assert_eq!(Facedir::from_param2(23).unwrap().to_param2(), 23);
+    }
+
+    #[test]
+    fn test_rotate_y() {
+        for param2 in 0..24 {
+            let facedir = Facedir::from_param2(param2).unwrap();
+
+            assert_eq!(
+                facedir
+                    .rotate_y_left()
+                    .rotate_y_left()
+                    .rotate_y_left()
+                    .rotate_y_left(),
+                facedir
+            );
+            assert_eq!(facedir.rotate_y_left().rotate_y_right(), facedir);
+            assert_eq!(
+                facedir.rotate_y_left().rotate_y_left(),
+                facedir.rotate_y_180()
+            );
+        }
+
+        assert_eq!(Facedir(0).rotate_y_left(), Facedir(1));
+        assert_eq!(Facedir(4).rotate_y_right(), Facedir(19));
+    }
+
+    #[test]
+    fn test_node_facedir() {
+        let node = Node::new("stairs:stair_wood".into(), Default::default(), true, 2);
+
+        assert_eq!(node.facedir(), Some(Facedir(2)));
+    }
+}
@@ -0,0 +1,742 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::iter::zip;
+
+use ndarray::{Array2, Array3, ArrayView3, Axis, s};
+
+use crate::error::Error;
+use crate::node::{NodeSpace, RawNode, SpawnProbability};
+use crate::vector::MapVector;
+
+use super::Schematic;
+
+/// Possible authoring mistakes in a [Schematic], as found by
+/// [Schematic::lint()](Schematic::lint).
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Lint {
+    /// The node at `coordinates` has `force_placement` set, while its spawn probability is lower
+    /// than [SpawnProbability::Always]. The node will then replace whatever is in the world, but
+    /// only some of the time.
+    ForcedProbabilisticNode { coordinates: MapVector },
+    /// The content `name` is registered in the schematic, but no node uses it.
+    UnusedContent { name: String },
+}
+
+/// Returns, for each content ID in the `schematic`, whether at least one node uses that content.
+pub(super) fn used_content_ids(schematic: &Schematic) -> Vec<bool> {
+    let mut used = vec![false; schematic.content_names.len()];
+
+    for node in &schematic.nodes {
+        if let Some(is_used) = used.get_mut(node.content_id as usize) {
+            *is_used = true;
+        }
+    }
+
+    used
+}
+
+/// Overview of the most important properties of a [Schematic], as returned by
+/// [Schematic::summary()](Schematic::summary).
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SchematicSummary {
+    pub dimensions: MapVector,
+    pub num_nodes: usize,
+    /// Number of different kinds of content used by the nodes, not counting "air".
+    pub distinct_materials: usize,
+    /// Fraction (from 0.0 to 1.0) of the nodes that are not "air".
+    pub density: f64,
+    pub version: u16,
+}
+
+impl fmt::Display for SchematicSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}x{}x{} schematic (version {}): {} nodes, {} distinct materials, {:.1}% filled",
+            self.dimensions.x,
+            self.dimensions.y,
+            self.dimensions.z,
+            self.version,
+            self.num_nodes,
+            self.distinct_materials,
+            self.density * 100.0
+        )
+    }
+}
+
+pub(super) fn summary(schematic: &Schematic) -> SchematicSummary {
+    let used = used_content_ids(schematic);
+    let distinct_materials = zip(&schematic.content_names, used)
+        .filter(|(name, used)| *used && *name != "air")
+        .count();
+
+    let num_nodes = schematic.nodes.len();
+    let num_non_air_nodes = schematic
+        .nodes
+        .iter()
+        .filter(|node| Some(node.content_id) != schematic.air_id)
+        .count();
+    let density = if num_nodes == 0 {
+        0.0
+    } else {
+        num_non_air_nodes as f64 / num_nodes as f64
+    };
+
+    SchematicSummary {
+        dimensions: schematic.dimensions,
+        num_nodes,
+        distinct_materials,
+        density,
+        version: schematic.version,
+    }
+}
+
+/// Which symmetries a [Schematic] has, as found by [Schematic::symmetry()](Schematic::symmetry).
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SymmetryReport {
+    /// Mirroring along the X-axis (i.e. swapping west and east) gives the same schematic.
+    pub mirror_x: bool,
+    /// Mirroring along the Y-axis (i.e. upside down) gives the same schematic.
+    pub mirror_y: bool,
+    /// Mirroring along the Z-axis (i.e. swapping north and south) gives the same schematic.
+    pub mirror_z: bool,
+    /// Rotating 180 degrees around the Y-axis gives the same schematic.
+    pub rotation_180: bool,
+}
+
+pub(super) fn symmetry(schematic: &Schematic) -> SymmetryReport {
+    let mirrored = |axes: &[Axis]| {
+        let mut view = schematic.nodes.view();
+        for axis in axes {
+            view.invert_axis(*axis);
+        }
+
+        has_same_contents(schematic, view)
+    };
+
+    SymmetryReport {
+        mirror_x: mirrored(&[Axis(2)]),
+        mirror_y: mirrored(&[Axis(1)]),
+        mirror_z: mirrored(&[Axis(0)]),
+        rotation_180: has_same_contents(schematic, schematic.rotate_180().nodes_view),
+    }
+}
+
+/// Whether the nodes of the `schematic` have the same content as the `other_nodes` view of it, at
+/// every position. Only the content names are compared, not param2 or probabilities.
+fn has_same_contents(schematic: &Schematic, other_nodes: ArrayView3<RawNode>) -> bool {
+    zip(&schematic.nodes, other_nodes).all(|(node, other_node)| {
+        schematic.content_name_for_id(node.content_id)
+            == schematic.content_name_for_id(other_node.content_id)
+    })
+}
+
+pub(super) fn lint(schematic: &Schematic) -> Vec<Lint> {
+    let mut lints = Vec::new();
+    let mut used = vec![false; schematic.content_names.len()];
+
+    for ((z, y, x), node) in schematic.nodes.indexed_iter() {
+        if let Some(is_used) = used.get_mut(node.content_id as usize) {
+            *is_used = true;
+        }
+
+        if node.force_placement
+            && SpawnProbability::from(node.spawn_probability) != SpawnProbability::Always
+        {
+            lints.push(Lint::ForcedProbabilisticNode {
+                coordinates: MapVector {
+                    x: x as u16,
+                    y: y as u16,
+                    z: z as u16,
+                },
+            });
+        }
+    }
+
+    // "air" is always registered, so there's no point in reporting it
+    lints.extend(
+        zip(&schematic.content_names, used)
+            .filter(|(name, used)| !used && *name != "air")
+            .map(|(name, _used)| Lint::UnusedContent {
+                name: name.to_string(),
+            }),
+    );
+
+    lints
+}
+
+/// Counts how many of the `nodes` there are of each content.
+fn content_histogram<'nodes>(
+    schematic: &Schematic,
+    nodes: impl IntoIterator<Item = &'nodes RawNode>,
+) -> HashMap<String, usize> {
+    // Counting by content ID first saves looking up and copying the name for every node
+    let mut content_id_counts: HashMap<u16, usize> = HashMap::new();
+    for node in nodes {
+        *content_id_counts.entry(node.content_id).or_default() += 1;
+    }
+
+    content_id_counts
+        .into_iter()
+        .filter_map(|(content_id, count)| {
+            Some((
+                schematic.content_name_for_id(content_id)?.to_string(),
+                count,
+            ))
+        })
+        .collect()
+}
+
+pub(super) fn layer_compositions(schematic: &Schematic) -> Vec<HashMap<String, usize>> {
+    schematic
+        .nodes
+        .axis_iter(Axis(1))
+        .map(|layer| content_histogram(schematic, layer))
+        .collect()
+}
+
+pub(super) fn placement_stats(schematic: &Schematic) -> (usize, usize, usize) {
+    let mut always_forced = 0;
+    let mut optional = 0;
+    let mut never = 0;
+
+    for raw_node in &schematic.nodes {
+        match SpawnProbability::from(raw_node.spawn_probability) {
+            SpawnProbability::Never => never += 1,
+            SpawnProbability::Always if raw_node.force_placement => always_forced += 1,
+            _ => optional += 1,
+        }
+    }
+
+    (always_forced, optional, never)
+}
+
+pub(super) fn connected_components(
+    schematic: &Schematic,
+    name: &str,
+) -> Result<Vec<Vec<MapVector>>, Error> {
+    let content_id = schematic
+        .content_id_for_name(name)
+        .ok_or_else(|| Error::InvalidContentName(name.to_string()))?;
+
+    let mut visited = Array3::from_elem(schematic.nodes.raw_dim(), false);
+    let mut components = Vec::new();
+
+    for (index, raw_node) in schematic.nodes.indexed_iter() {
+        if raw_node.content_id != content_id || visited[index] {
+            continue;
+        }
+
+        // Breadth-first search with a queue instead of recursion, so big components can't
+        // overflow the stack
+        let mut component = Vec::new();
+        let mut queue = VecDeque::from([MapVector::from_shape(index)?]);
+        visited[index] = true;
+
+        while let Some(coordinates) = queue.pop_front() {
+            component.push(coordinates);
+
+            for neighbor in schematic.neighbor_coordinates(coordinates) {
+                let neighbor_index = neighbor.as_shape();
+                if !visited[neighbor_index]
+                    && schematic.nodes[neighbor_index].content_id == content_id
+                {
+                    visited[neighbor_index] = true;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        components.push(component);
+    }
+
+    Ok(components)
+}
+
+pub(super) fn surface_heightmap(schematic: &Schematic) -> Array2<Option<u16>> {
+    let (size_z, _size_y, size_x) = schematic.nodes.dim();
+
+    Array2::from_shape_fn((size_x, size_z), |(x, z)| {
+        schematic
+            .nodes
+            .slice(s![z, .., x])
+            .iter()
+            .rposition(|raw_node| Some(raw_node.content_id) != schematic.air_id)
+            .map(|y| y as u16)
+    })
+}
+
+/// Returns the content names that are registered in every one of the given `schematics`, e.g. to
+/// estimate how big the content table of their merged result will be. Returns an empty set when no
+/// `schematics` are given.
+pub fn common_content(schematics: &[Schematic]) -> HashSet<&str> {
+    let Some((first, others)) = schematics.split_first() else {
+        return HashSet::new();
+    };
+
+    let mut common: HashSet<&str> = first
+        .content_names
+        .iter()
+        .map(|name| name.as_str())
+        .collect();
+    for schematic in others {
+        let names: HashSet<&str> = schematic
+            .content_names
+            .iter()
+            .map(|name| name.as_str())
+            .collect();
+        common.retain(|name| names.contains(name));
+    }
+
+    common
+}
+
+pub(super) fn identical_layer_runs(schematic: &Schematic) -> Vec<(u16, u16)> {
+    let is_same_layer = |y_1: usize, y_2: usize| {
+        zip(
+            schematic.nodes.index_axis(Axis(1), y_1),
+            schematic.nodes.index_axis(Axis(1), y_2),
+        )
+        .all(|(node_1, node_2)| {
+            node_1.param2 == node_2.param2
+                && schematic.content_name_for_id(node_1.content_id)
+                    == schematic.content_name_for_id(node_2.content_id)
+        })
+    };
+
+    let mut runs = Vec::new();
+    let mut run_start = 0;
+    for y in 1..=schematic.dimensions.y as usize {
+        if y < schematic.dimensions.y as usize && is_same_layer(run_start, y) {
+            continue;
+        }
+
+        let count = y - run_start;
+        if count > 1 {
+            runs.push((run_start as u16, count as u16));
+        }
+        run_start = y;
+    }
+
+    runs
+}
+
+pub(super) fn bounding_box_of(
+    schematic: &Schematic,
+    name: &str,
+) -> Result<Option<(MapVector, MapVector)>, Error> {
+    let content_id = schematic
+        .content_id_for_name(name)
+        .ok_or_else(|| Error::InvalidContentName(name.to_string()))?;
+
+    let mut bounds: Option<([usize; 3], [usize; 3])> = None;
+    for ((z, y, x), _node) in schematic
+        .nodes
+        .indexed_iter()
+        .filter(|(_index, node)| node.content_id == content_id)
+    {
+        let position = [x, y, z];
+        bounds = Some(match bounds {
+            None => (position, position),
+            Some((min, max)) => (
+                std::array::from_fn(|axis| min[axis].min(position[axis])),
+                std::array::from_fn(|axis| max[axis].max(position[axis])),
+            ),
+        });
+    }
+
+    Ok(bounds.map(|(min, max)| {
+        let to_map_vector = |[x, y, z]: [usize; 3]| MapVector {
+            x: x as u16,
+            y: y as u16,
+            z: z as u16,
+        };
+
+        (to_map_vector(min), to_map_vector(max))
+    }))
+}
+
+pub(super) fn equals_layout(schematic: &Schematic, other: &Schematic) -> bool {
+    schematic.dimensions == other.dimensions
+        && zip(&schematic.nodes, &other.nodes).all(|(node, other_node)| {
+            node.param2 == other_node.param2
+                && schematic.content_name_for_id(node.content_id)
+                    == other.content_name_for_id(other_node.content_id)
+        })
+}
+
+pub(super) fn used_content_names(schematic: &Schematic) -> impl Iterator<Item = &str> {
+    zip(&schematic.content_names, used_content_ids(schematic))
+        .filter(|(_name, used)| *used)
+        .map(|(name, _used)| name.as_str())
+}
+
+pub(super) fn uses_only(schematic: &Schematic, allowed: &HashSet<&str>) -> Result<(), Vec<String>> {
+    let disallowed: Vec<String> = zip(&schematic.content_names, used_content_ids(schematic))
+        .filter(|(name, used)| *used && *name != "air" && !allowed.contains(name.as_str()))
+        .map(|(name, _used)| name.to_string())
+        .collect();
+
+    if disallowed.is_empty() {
+        Ok(())
+    } else {
+        Err(disallowed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::node::{Node, RawNode};
+
+    #[test]
+    fn test_used_content_names() {
+        let mut schematic = Schematic::new((2, 2, 2).try_into().unwrap()).unwrap();
+        schematic.register_content("default:lava_source".into());
+        schematic
+            .place_node(
+                &Node::with_content_name("default:dirt".into()),
+                (0, 0, 0).try_into().unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            schematic.used_content_names().collect::<Vec<&str>>(),
+            &["air", "default:dirt"]
+        );
+    }
+
+    #[test]
+    fn test_placement_stats() {
+        let mut schematic = Schematic::new((2, 2, 2).try_into().unwrap()).unwrap();
+        let nodes = [
+            Node::new("default:stone".into(), SpawnProbability::Always, true, 0),
+            Node::new(
+                "default:stone".into(),
+                SpawnProbability::Custom(64),
+                true,
+                0,
+            ),
+            Node::new("default:stone".into(), SpawnProbability::Never, true, 0),
+        ];
+        for (x, node) in nodes.iter().enumerate() {
+            schematic
+                .place_node(node, (x as u16 % 2, x as u16 / 2, 0).try_into().unwrap())
+                .unwrap();
+        }
+
+        let (always_forced, optional, never) = schematic.placement_stats();
+
+        // The remaining air nodes don't replace anything
+        assert_eq!(always_forced, 1);
+        assert_eq!(optional, 6);
+        assert_eq!(never, 1);
+        assert_eq!(always_forced + optional + never, schematic.num_nodes());
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let mut schematic = Schematic::new((6, 3, 3).try_into().unwrap()).unwrap();
+        let ore = Node::with_content_name("default:stone_with_iron".into());
+        // A 2x2x2 blob in one corner, and an L-shaped one in the other, only touching diagonally
+        schematic
+            .fill(
+                (0, 0, 0).try_into().unwrap(),
+                (2, 2, 2).try_into().unwrap(),
+                &ore,
+            )
+            .unwrap();
+        for coordinates in [(2, 2, 2), (3, 2, 2), (4, 2, 2), (4, 1, 2)] {
+            schematic
+                .place_node(&ore, coordinates.try_into().unwrap())
+                .unwrap();
+        }
+
+        let components = schematic
+            .connected_components("default:stone_with_iron")
+            .unwrap();
+
+        let sizes: Vec<usize> = components.iter().map(Vec::len).collect();
+        assert_eq!(sizes, [8, 4]);
+        assert!(components[1].contains(&(4, 1, 2).try_into().unwrap()));
+
+        assert_eq!(schematic.connected_components("air").unwrap().len(), 1);
+        schematic
+            .connected_components("default:stone_with_gold")
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_layer_compositions() {
+        let mut schematic = Schematic::new((3, 2, 2).try_into().unwrap()).unwrap();
+        schematic
+            .fill(
+                (0, 0, 0).try_into().unwrap(),
+                (3, 1, 2).try_into().unwrap(),
+                &Node::with_content_name("default:cobble".into()),
+            )
+            .unwrap();
+
+        let layer_compositions = schematic.layer_compositions();
+
+        assert_eq!(
+            layer_compositions,
+            &[
+                HashMap::from([("default:cobble".to_string(), 6)]),
+                HashMap::from([("air".to_string(), 6)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_surface_heightmap() {
+        let mut schematic = Schematic::new((3, 4, 2).try_into().unwrap()).unwrap();
+        let dirt = Node::with_content_name("default:dirt".into());
+        // A stepped surface rising along X, with the column at (2, 1) left empty
+        for (x, z, height) in [(0, 0, 1), (1, 0, 2), (2, 0, 4), (0, 1, 1), (1, 1, 3)] {
+            for y in 0..height {
+                schematic
+                    .place_node(&dirt, (x, y, z).try_into().unwrap())
+                    .unwrap();
+            }
+        }
+
+        let heightmap = schematic.surface_heightmap();
+
+        assert_eq!(
+            heightmap,
+            ndarray::array![[Some(0), Some(0)], [Some(1), Some(2)], [Some(3), None]]
+        );
+    }
+
+    #[test]
+    fn test_common_content() {
+        let mut schematic_1 = Schematic::new((1, 1, 1).try_into().unwrap()).unwrap();
+        schematic_1.register_content("default:stone".into());
+        schematic_1.register_content("default:dirt".into());
+
+        let mut schematic_2 = Schematic::new((1, 1, 1).try_into().unwrap()).unwrap();
+        schematic_2.register_content("default:sand".into());
+        schematic_2.register_content("default:stone".into());
+
+        let schematics = [schematic_1, schematic_2];
+
+        assert_eq!(
+            common_content(&schematics),
+            HashSet::from(["air", "default:stone"])
+        );
+        assert_eq!(
+            common_content(&schematics[..1]),
+            HashSet::from(["air", "default:stone", "default:dirt"])
+        );
+        assert!(common_content(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_symmetry() {
+        let mut schematic = Schematic::new((3, 2, 2).try_into().unwrap()).unwrap();
+        let stone = Node::with_content_name("default:stone".into());
+        // Both ends of the X-axis on the bottom layer, only at the front
+        for x in [0, 2] {
+            schematic
+                .place_node(&stone, (x, 0, 0).try_into().unwrap())
+                .unwrap();
+        }
+
+        assert_eq!(
+            schematic.symmetry(),
+            SymmetryReport {
+                mirror_x: true,
+                mirror_y: false,
+                mirror_z: false,
+                rotation_180: false,
+            }
+        );
+
+        schematic
+            .place_node(&stone, (0, 0, 1).try_into().unwrap())
+            .unwrap();
+        assert!(!schematic.symmetry().mirror_x);
+
+        let mut schematic = Schematic::new((2, 1, 2).try_into().unwrap()).unwrap();
+        for (x, z) in [(0, 0), (1, 1)] {
+            schematic
+                .place_node(&stone, (x, 0, z).try_into().unwrap())
+                .unwrap();
+        }
+
+        assert_eq!(
+            schematic.symmetry(),
+            SymmetryReport {
+                mirror_x: false,
+                mirror_y: true,
+                mirror_z: false,
+                rotation_180: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_identical_layer_runs() {
+        let mut schematic = Schematic::new((2, 5, 2).try_into().unwrap()).unwrap();
+        let wood = Node::with_content_name("default:wood".into());
+        // Layers 1 and 2 are the same, as are 3 and 4 (both air)
+        for y in [1, 2] {
+            schematic
+                .fill(
+                    (0, y, 0).try_into().unwrap(),
+                    (2, 1, 2).try_into().unwrap(),
+                    &wood,
+                )
+                .unwrap();
+        }
+
+        assert_eq!(schematic.identical_layer_runs(), &[(1, 2), (3, 2)]);
+
+        // A different param2 makes layers differ
+        schematic
+            .place_node(
+                &Node::new("default:wood".into(), SpawnProbability::Always, false, 1),
+                (1, 2, 1).try_into().unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(schematic.identical_layer_runs(), &[(3, 2)]);
+    }
+
+    #[test]
+    fn test_bounding_box_of() {
+        let mut schematic = Schematic::new((6, 6, 6).try_into().unwrap()).unwrap();
+        let cobble = Node::with_content_name("default:cobble".into());
+        for (x, y, z) in [(1, 4, 2), (3, 1, 5), (2, 2, 1)] {
+            schematic
+                .place_node(&cobble, (x, y, z).try_into().unwrap())
+                .unwrap();
+        }
+        schematic.register_content("default:water_source".into());
+
+        assert_eq!(
+            schematic.bounding_box_of("default:cobble").unwrap(),
+            Some((
+                MapVector::new(1, 1, 1).unwrap(),
+                MapVector::new(3, 4, 5).unwrap()
+            ))
+        );
+        assert_eq!(
+            schematic.bounding_box_of("default:water_source").unwrap(),
+            None
+        );
+        assert!(matches!(
+            schematic.bounding_box_of("default:lava_source"),
+            Err(Error::InvalidContentName(name)) if name == "default:lava_source"
+        ));
+    }
+
+    #[test]
+    fn test_equals_layout() {
+        let mut schematic = Schematic::new((2, 2, 2).try_into().unwrap()).unwrap();
+        schematic
+            .place_node(
+                &Node::new("default:dirt".into(), SpawnProbability::Always, true, 3),
+                (1, 0, 0).try_into().unwrap(),
+            )
+            .unwrap();
+
+        // Same layout, but different probabilities, and content registered in a different order
+        let mut other_schematic = Schematic::new((2, 2, 2).try_into().unwrap()).unwrap();
+        other_schematic.register_content("default:stone".into());
+        other_schematic
+            .place_node(
+                &Node::new(
+                    "default:dirt".into(),
+                    SpawnProbability::Custom(20),
+                    false,
+                    3,
+                ),
+                (1, 0, 0).try_into().unwrap(),
+            )
+            .unwrap();
+        other_schematic.layer_probabilities[1] = SpawnProbability::Never;
+
+        assert!(schematic.equals_layout(&other_schematic));
+        assert_ne!(schematic, other_schematic);
+
+        other_schematic.nodes[(0, 0, 1)].param2 = 0;
+        assert!(!schematic.equals_layout(&other_schematic));
+    }
+
+    #[test]
+    fn test_uses_only() {
+        let mut schematic = Schematic::new((2, 2, 2).try_into().unwrap()).unwrap();
+        schematic
+            .place_node(
+                &Node::with_content_name("default:dirt".into()),
+                (0, 0, 0).try_into().unwrap(),
+            )
+            .unwrap();
+        schematic
+            .place_node(
+                &Node::with_content_name("default:cobble".into()),
+                (1, 0, 0).try_into().unwrap(),
+            )
+            .unwrap();
+        // Registered, but not used by any node
+        schematic.register_content("default:lava_source".into());
+
+        assert_eq!(
+            schematic.uses_only(&HashSet::from(["default:dirt", "default:cobble"])),
+            Ok(())
+        );
+        assert_eq!(
+            schematic.uses_only(&HashSet::from(["default:dirt"])),
+            Err(vec!["default:cobble".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_summary() {
+        let data = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/3x3.mts"));
+        let schematic = Schematic::from_bytes(data).unwrap();
+
+        let summary = schematic.summary();
+
+        assert_eq!(
+            summary,
+            SchematicSummary {
+                dimensions: (3, 2, 3).try_into().unwrap(),
+                num_nodes: 18,
+                distinct_materials: 6,
+                density: 0.5,
+                version: 4,
+            }
+        );
+        assert_eq!(
+            summary.to_string(),
+            "3x2x3 schematic (version 4): 18 nodes, 6 distinct materials, 50.0% filled"
+        );
+    }
+
+    #[test]
+    fn test_lint() {
+        let mut schematic = Schematic::new((2, 2, 2).try_into().unwrap()).unwrap();
+        let content_id = schematic.register_content("default:apple".into());
+        schematic.register_content("default:lava_source".into());
+        let coordinates: MapVector = (1, 0, 0).try_into().unwrap();
+        schematic.nodes[coordinates.as_shape()] =
+            RawNode::new(content_id, SpawnProbability::Custom(50), true, 0);
+
+        assert_eq!(
+            schematic.lint(),
+            &[
+                Lint::ForcedProbabilisticNode { coordinates },
+                Lint::UnusedContent {
+                    name: "default:lava_source".to_string()
+                }
+            ]
+        );
+    }
+}
@@ -0,0 +1,268 @@
+use std::ops::Deref;
+
+use ndarray::s;
+
+use crate::error::Error;
+use crate::node::{Node, NodeSpace, RawNode};
+use crate::vector::MapVector;
+
+use super::Schematic;
+
+/// A change to a single node, as recorded by [ChangeRecorder]: its coordinates, and the node
+/// before and after the change.
+pub type NodeChange = (MapVector, RawNode, RawNode);
+
+/// Edits a [Schematic] while keeping track of every node that changes, e.g. for undo and redo in
+/// an editor. Created by [Schematic::record_changes()].
+///
+/// Only the edits made through the recorder are recorded. It dereferences to the `Schematic`, so
+/// it can still be read from while recording.
+///
+/// The recorded [RawNode]s keep pointing to the right content, as registering content never
+/// changes existing content IDs, so they can be undone or redone with
+/// [Schematic::apply_changes()].
+pub struct ChangeRecorder<'schematic> {
+    schematic: &'schematic mut Schematic,
+    changes: Vec<NodeChange>,
+}
+
+impl<'schematic> ChangeRecorder<'schematic> {
+    pub(super) fn new(schematic: &'schematic mut Schematic) -> Self {
+        ChangeRecorder {
+            schematic,
+            changes: Vec::new(),
+        }
+    }
+
+    /// Records the changes of [Schematic::place_node()].
+    pub fn place_node(&mut self, node: &Node, coordinates: MapVector) -> Result<(), Error> {
+        self.record_region(coordinates, MapVector { x: 1, y: 1, z: 1 }, |schematic| {
+            schematic.place_node(node, coordinates)
+        })
+    }
+
+    /// Records the changes of [Schematic::fill()].
+    pub fn fill(
+        &mut self,
+        from_position: MapVector,
+        fill_space: MapVector,
+        node: &Node,
+    ) -> Result<(), Error> {
+        self.record_region(from_position, fill_space, |schematic| {
+            schematic.fill(from_position, fill_space, node)
+        })
+    }
+
+    /// Records the changes of [Schematic::merge()].
+    pub fn merge<'source>(
+        &mut self,
+        source: &'source impl NodeSpace<'source>,
+        merge_at: MapVector,
+    ) -> Result<(), Error> {
+        self.record_region(merge_at, source.dimensions(), |schematic| {
+            schematic.merge(source, merge_at)
+        })
+    }
+
+    /// The changes recorded so far, from the oldest to the newest.
+    pub fn changes(&self) -> &[NodeChange] {
+        &self.changes
+    }
+
+    /// Stops recording, and returns the recorded changes from the oldest to the newest.
+    pub fn into_changes(self) -> Vec<NodeChange> {
+        self.changes
+    }
+
+    /// Runs `edit`, and records the nodes it changed within the region of `size` starting at
+    /// `from`. Nodes outside of that region are expected to stay the same.
+    fn record_region<F: FnOnce(&mut Schematic) -> Result<(), Error>>(
+        &mut self,
+        from: MapVector,
+        size: MapVector,
+        edit: F,
+    ) -> Result<(), Error> {
+        // Only the part of the region within the Schematic can change, and when the region doesn't
+        // fit at all the edit returns an error anyway
+        let (size_z, size_y, size_x) = self.schematic.dimensions.as_shape();
+        let (from_z, from_y, from_x) = from.as_shape();
+        let (region_z, region_y, region_x) = size.as_shape();
+        let slice = s![
+            from_z.min(size_z)..(from_z + region_z).min(size_z),
+            from_y.min(size_y)..(from_y + region_y).min(size_y),
+            from_x.min(size_x)..(from_x + region_x).min(size_x)
+        ];
+        let old_nodes = self.schematic.nodes.slice(slice).to_owned();
+
+        edit(self.schematic)?;
+
+        let new_nodes = self.schematic.nodes.slice(slice);
+        for ((z, y, x), old_node) in old_nodes.indexed_iter() {
+            let new_node = new_nodes[(z, y, x)];
+            if *old_node != new_node {
+                let coordinates = MapVector {
+                    x: (from_x + x) as u16,
+                    y: (from_y + y) as u16,
+                    z: (from_z + z) as u16,
+                };
+                self.changes.push((coordinates, *old_node, new_node));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Deref for ChangeRecorder<'_> {
+    type Target = Schematic;
+
+    fn deref(&self) -> &Schematic {
+        self.schematic
+    }
+}
+
+pub(super) fn apply_changes(
+    schematic: &mut Schematic,
+    changes: &[(MapVector, RawNode)],
+) -> Result<(), Error> {
+    // Check everything first, so the changes are either all applied or none of them
+    for (coordinates, node) in changes {
+        if schematic.raw_node_at(*coordinates).is_none() {
+            return Err(Error::OutOfBounds);
+        }
+        if schematic.content_name_for_id(node.content_id).is_none() {
+            return Err(Error::InvalidContentIndex(node.content_id));
+        }
+    }
+
+    for (coordinates, node) in changes {
+        schematic.nodes[coordinates.as_shape()] = *node;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::node::SpawnProbability;
+
+    #[test]
+    fn test_record_fill() {
+        let mut schematic = Schematic::new((3, 3, 3).try_into().unwrap()).unwrap();
+        let air = *schematic.raw_node_at(MapVector::ZERO).unwrap();
+
+        let mut recorder = schematic.record_changes();
+        recorder
+            .fill(
+                (1, 0, 1).try_into().unwrap(),
+                (2, 2, 2).try_into().unwrap(),
+                &Node::with_content_name("default:stone".into()),
+            )
+            .unwrap();
+        let stone_id = recorder.content_id_for_name("default:stone").unwrap();
+        let changes = recorder.into_changes();
+
+        let stone = RawNode::new(stone_id, SpawnProbability::Always, true, 0);
+        let mut expected_changes = Vec::new();
+        for z in 1..3 {
+            for y in 0..2 {
+                for x in 1..3 {
+                    expected_changes.push((MapVector::new(x, y, z).unwrap(), air, stone));
+                }
+            }
+        }
+        assert_eq!(changes, expected_changes);
+    }
+
+    #[test]
+    fn test_undo_and_redo() {
+        let mut schematic = Schematic::new((3, 3, 3).try_into().unwrap()).unwrap();
+        schematic
+            .place_node(
+                &Node::with_content_name("default:dirt".into()),
+                (1, 1, 1).try_into().unwrap(),
+            )
+            .unwrap();
+        let original_nodes = schematic.nodes.clone();
+
+        let mut recorder = schematic.record_changes();
+        recorder
+            .fill(
+                MapVector::ZERO,
+                (2, 2, 2).try_into().unwrap(),
+                &Node::with_content_name("default:stone".into()),
+            )
+            .unwrap();
+        let changes = recorder.into_changes();
+        let filled_nodes = schematic.nodes.clone();
+
+        let undo: Vec<(MapVector, RawNode)> = changes
+            .iter()
+            .rev()
+            .map(|(coordinates, old_node, _new_node)| (*coordinates, *old_node))
+            .collect();
+        schematic.apply_changes(&undo).unwrap();
+        assert_eq!(schematic.nodes, original_nodes);
+
+        let redo: Vec<(MapVector, RawNode)> = changes
+            .iter()
+            .map(|(coordinates, _old_node, new_node)| (*coordinates, *new_node))
+            .collect();
+        schematic.apply_changes(&redo).unwrap();
+        assert_eq!(schematic.nodes, filled_nodes);
+    }
+
+    #[test]
+    fn test_apply_invalid_changes() {
+        let mut schematic = Schematic::new((2, 2, 2).try_into().unwrap()).unwrap();
+        let air = RawNode::with_content_id(0);
+
+        let out_of_bounds = [
+            (MapVector::ZERO, air),
+            (MapVector::new(0, 2, 0).unwrap(), air),
+        ];
+        assert!(matches!(
+            schematic.apply_changes(&out_of_bounds),
+            Err(Error::OutOfBounds)
+        ));
+
+        let unknown_content = [(MapVector::ZERO, RawNode::with_content_id(1))];
+        assert!(matches!(
+            schematic.apply_changes(&unknown_content),
+            Err(Error::InvalidContentIndex(1))
+        ));
+        assert!(schematic.nodes.iter().all(|node| node.content_id == 0));
+    }
+
+    #[test]
+    fn test_record_only_changed_nodes() {
+        let mut schematic = Schematic::new((4, 1, 1).try_into().unwrap()).unwrap();
+        let stone = Node::with_content_name("default:stone".into());
+        schematic
+            .place_node(&stone, (0, 0, 0).try_into().unwrap())
+            .unwrap();
+
+        let mut recorder = schematic.record_changes();
+        recorder
+            .fill(MapVector::ZERO, (2, 1, 1).try_into().unwrap(), &stone)
+            .unwrap();
+        recorder
+            .place_node(&stone, (3, 0, 0).try_into().unwrap())
+            .unwrap();
+
+        let changed_coordinates: Vec<MapVector> = recorder
+            .changes()
+            .iter()
+            .map(|(coordinates, _old_node, _new_node)| *coordinates)
+            .collect();
+        assert_eq!(
+            changed_coordinates,
+            [
+                MapVector::new(1, 0, 0).unwrap(),
+                MapVector::new(3, 0, 0).unwrap()
+            ]
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use std::borrow::Borrow;
+use std::fmt;
+use std::ops::Deref;
+
+#[cfg(feature = "intern")]
+use std::collections::HashSet;
+#[cfg(feature = "intern")]
+use std::sync::{Arc, LazyLock, Mutex};
+
+/// All content names that are in use, shared by every [Schematic](super::Schematic).
+#[cfg(feature = "intern")]
+static POOL: LazyLock<Mutex<Pool>> = LazyLock::new(Default::default);
+
+/// The pool never shrinks below this size, so a few names being used and dropped over and over
+/// don't make it prune all the time.
+#[cfg(feature = "intern")]
+const MIN_POOL_PRUNE_SIZE: usize = 1024;
+
+/// Interned content names. Names that are only held by the pool itself are no longer used by any
+/// `Schematic`, and are pruned whenever the pool has doubled in size since the last time, so the
+/// pool doesn't keep growing when a long running program loads a lot of different schematics.
+#[cfg(feature = "intern")]
+#[derive(Debug)]
+struct Pool {
+    names: HashSet<Arc<str>>,
+    prune_at: usize,
+}
+
+#[cfg(feature = "intern")]
+impl Default for Pool {
+    fn default() -> Self {
+        Pool {
+            names: HashSet::new(),
+            prune_at: MIN_POOL_PRUNE_SIZE,
+        }
+    }
+}
+
+#[cfg(feature = "intern")]
+impl Pool {
+    fn intern(&mut self, name: String) -> Arc<str> {
+        if let Some(interned) = self.names.get(name.as_str()) {
+            return Arc::clone(interned);
+        }
+
+        if self.names.len() >= self.prune_at {
+            self.prune();
+        }
+        let interned: Arc<str> = name.into();
+        self.names.insert(Arc::clone(&interned));
+
+        interned
+    }
+
+    /// Removes the names that nothing but the pool holds on to. Those can't be cloned while the
+    /// pool is locked, as that requires getting them from the pool first.
+    fn prune(&mut self) {
+        self.names.retain(|name| Arc::strong_count(name) > 1);
+        self.prune_at = (self.names.len() * 2).max(MIN_POOL_PRUNE_SIZE);
+    }
+}
+
+/// A name of content in a [Schematic](super::Schematic), like "default:cobble".
+///
+/// With the `intern` feature, equal names share a single allocation across all `Schematic`s,
+/// which saves memory and makes cloning cheap when handling a lot of schematics. Otherwise it's a
+/// plain `String`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "String", into = "String")
+)]
+pub(crate) struct ContentName(
+    #[cfg(not(feature = "intern"))] String,
+    #[cfg(feature = "intern")] Arc<str>,
+);
+
+impl ContentName {
+    pub(crate) fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(not(feature = "intern"))]
+impl From<String> for ContentName {
+    fn from(name: String) -> Self {
+        ContentName(name)
+    }
+}
+
+#[cfg(feature = "intern")]
+impl From<String> for ContentName {
+    fn from(name: String) -> Self {
+        // A poisoned lock can only happen when another thread panicked while inserting, which
+        // leaves the set itself intact.
+        let mut pool = POOL.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        ContentName(pool.intern(name))
+    }
+}
+
+impl From<&str> for ContentName {
+    fn from(name: &str) -> Self {
+        ContentName::from(name.to_string())
+    }
+}
+
+impl From<ContentName> for String {
+    fn from(name: ContentName) -> Self {
+        name.0.to_string()
+    }
+}
+
+impl Deref for ContentName {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for ContentName {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq<str> for ContentName {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for ContentName {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl fmt::Display for ContentName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_with_str() {
+        let name = ContentName::from("default:cobble");
+
+        assert_eq!(name, "default:cobble");
+        assert_ne!(name, "default:dirt");
+        assert_eq!(name.to_string(), "default:cobble");
+    }
+
+    #[cfg(feature = "intern")]
+    #[test]
+    fn test_interned_names_share_allocation() {
+        use crate::Schematic;
+
+        let mut schematic_1 = Schematic::new((1, 1, 1).try_into().unwrap()).unwrap();
+        let mut schematic_2 = Schematic::new((1, 1, 1).try_into().unwrap()).unwrap();
+
+        let id_1 = schematic_1.register_content("default:mese".into());
+        let id_2 = schematic_2.register_content("default:mese".to_string().into());
+
+        assert!(Arc::ptr_eq(
+            &schematic_1.content_names[id_1 as usize].0,
+            &schematic_2.content_names[id_2 as usize].0
+        ));
+    }
+
+    #[cfg(feature = "intern")]
+    #[test]
+    fn test_prune_unused_names() {
+        let lock_pool = || POOL.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let kept = ContentName::from("test:kept_while_pruning");
+        drop(ContentName::from("test:dropped_before_pruning"));
+
+        lock_pool().prune();
+
+        let pool = lock_pool();
+        assert!(pool.names.contains("test:kept_while_pruning"));
+        assert!(!pool.names.contains("test:dropped_before_pruning"));
+        assert!(pool.prune_at >= MIN_POOL_PRUNE_SIZE);
+        drop(pool);
+        assert_eq!(kept, "test:kept_while_pruning");
+    }
+}
@@ -1,12 +1,15 @@
 use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::sync::OnceLock;
 
-use ndarray::{Array3, AssignElem, s};
+use ndarray::{Array3, AssignElem, Zip, s};
 
 use crate::error::Error;
 use crate::node::{Node, NodeSpace, RawNode, SpawnProbability};
 use crate::vector::MapVector;
 
-use super::Schematic;
+use super::content_name::ContentName;
+use super::{Schematic, SchematicRef};
 
 pub(super) fn fill(
     destination: &mut Schematic,
@@ -17,7 +20,7 @@ pub(super) fn fill(
     let to: MapVector = from_position
         .checked_add(fill_space)
         .ok_or(Error::OutOfBounds)?;
-    if to > destination.dimensions {
+    if !to.fits_within(destination.dimensions) {
         return Err(Error::OutOfBounds);
     }
 
@@ -50,29 +53,33 @@ pub(super) fn insert_layer(
         .checked_add((0, 1, 0).try_into()?)
         .ok_or(Error::OutOfBounds)?;
 
-    let fill_with_raw_node = RawNode::new(
-        schematic.content_names.len() as u16,
-        fill_with_node.spawn_probability,
-        fill_with_node.force_placement,
-        fill_with_node.param2,
-    );
-
-    let extended_nodes = Array3::from_elem(new_dimensions.as_shape(), fill_with_raw_node);
-
     // TODO Like with from_bytes(), this could do with a better constructor
     let mut new_schematic = Schematic {
         version: schematic.version,
         dimensions: new_dimensions,
         layer_probabilities: schematic.layer_probabilities.clone(),
         content_names: schematic.content_names.clone(),
-        nodes: extended_nodes,
+        air_id: schematic.air_id,
+        ignore_id: schematic.ignore_id,
+        nodes: Array3::default(new_dimensions.as_shape()),
+        name: schematic.name.clone(),
+        digest: OnceLock::new(),
     };
+    // The content might already be in the schematic, which can't contain duplicate names
+    let fill_with_raw_node = RawNode::new(
+        new_schematic.register_content(fill_with_node.content_name.clone()),
+        fill_with_node.spawn_probability,
+        fill_with_node.force_placement,
+        fill_with_node.param2,
+    );
+
+    let y = y as usize;
     new_schematic
-        .content_names
-        .push(fill_with_node.content_name.clone().into_owned());
+        .nodes
+        .slice_mut(s![.., y, ..])
+        .fill(fill_with_raw_node);
 
     // Copy all nodes above the new layer
-    let y = y as usize;
     schematic
         .nodes
         .slice(s![.., 0..y, ..])
@@ -96,14 +103,68 @@ pub(super) fn merge<'schematic>(
     destination: &mut Schematic,
     merge_at: MapVector,
 ) -> Result<(), Error> {
+    merge_nodes(source, destination, merge_at, None, false)?;
+
+    Ok(())
+}
+
+pub(super) fn merge_returning_map(
+    source: &Schematic,
+    destination: &mut Schematic,
+    merge_at: MapVector,
+) -> Result<HashMap<u16, u16>, Error> {
+    let mut content_map = merge_nodes(source, destination, merge_at, None, false)?;
+    // Only the content IDs that changed are mapped while merging
+    for content_id in 0..source.content_names.len() as u16 {
+        content_map.entry(content_id).or_insert(content_id);
+    }
+
+    Ok(content_map)
+}
+
+pub(super) fn merge_ignore_source_air(
+    source: &Schematic,
+    destination: &mut Schematic,
+    merge_at: MapVector,
+) -> Result<(), Error> {
+    merge_nodes(source, destination, merge_at, None, true)?;
+
+    Ok(())
+}
+
+pub(super) fn merge_with_probability<'schematic>(
+    source: &'schematic impl NodeSpace<'schematic>,
+    destination: &mut Schematic,
+    merge_at: MapVector,
+    probability: SpawnProbability,
+) -> Result<(), Error> {
+    merge_nodes(source, destination, merge_at, Some(probability), false)?;
+
+    Ok(())
+}
+
+/// Does the actual merging for [merge()], [merge_ignore_source_air()] and
+/// [merge_with_probability()]. When `probability` is given, it replaces the spawn probability of
+/// every pasted node, except for those that the source marks as never to be placed. With
+/// `skip_source_air`, the "air" nodes of the source leave the destination untouched.
+///
+/// Returns the mapping from the source's content IDs to those in the destination, for the content
+/// IDs that differ.
+fn merge_nodes<'schematic>(
+    source: &'schematic impl NodeSpace<'schematic>,
+    destination: &mut Schematic,
+    merge_at: MapVector,
+    probability: Option<SpawnProbability>,
+    skip_source_air: bool,
+) -> Result<HashMap<u16, u16>, Error> {
     let merge_end = merge_at
         .checked_add(source.dimensions())
         .ok_or(Error::OutOfBounds)?;
-    if merge_end > destination.dimensions {
+    if !merge_end.fits_within(destination.dimensions) {
         return Err(Error::OutOfBounds);
     }
 
-    let current_content_positions: HashMap<String, usize> = destination
+    let current_content_positions: HashMap<ContentName, usize> = destination
         .content_names
         .iter()
         .cloned()
@@ -125,17 +186,21 @@ pub(super) fn merge<'schematic>(
             }
         } else {
             // Content isn't present in this Schematic yet
-            destination.content_names.push(content_name.to_string());
-            let new_content_id = destination.content_names.len() - 1;
-            source_content_map.insert(source_content_id as u16, new_content_id as u16);
+            let new_content_id = destination.push_content_name(content_name.to_string());
+            source_content_map.insert(source_content_id as u16, new_content_id);
         }
     }
 
     // These two content IDs are for blocks that are considered by Luanti as "nothing" when it
     // comes to deciding whether a node should overwrite the existing position, and the new node is
     // marked as "force_placement = false"
-    let content_air = destination.content_id_for_name("air");
-    let content_ignore = destination.content_id_for_name("ignore");
+    let content_air = destination.air_id;
+    let content_ignore = destination.ignore_id;
+    let source_content_air = if skip_source_air {
+        source.content_id_for_name("air")
+    } else {
+        None
+    };
 
     let from_shape = merge_at.as_shape();
     let to_shape = merge_end.as_shape();
@@ -153,29 +218,15 @@ pub(super) fn merge<'schematic>(
         // the target `into` slice into the closure, so we aren't able to make any comparisons
         // to the original node.
         .and(target_space)
-        .for_each(move |merge_node, target_node| {
+        .for_each(|merge_node, target_node| {
             // This doesn't take any SpawnProbability::Custom() probability into account, such
             // nodes will just overwrite the current node. The game will then decide whether to
             // spawn the node or not.
-            if merge_node.spawn_probability == SpawnProbability::Never.into()
-                && !merge_node.force_placement
+            if Some(merge_node.content_id) == source_content_air
+                || !is_placed_by_merge(merge_node, target_node, content_air, content_ignore)
             {
-                let place_merge_node = if let Some(air) = content_air
-                    && target_node.content_id == air
-                {
-                    true
-                } else if let Some(ignore) = content_ignore
-                    && target_node.content_id == ignore
-                {
-                    true
-                } else {
-                    false
-                };
-
-                if !place_merge_node {
-                    // Leave the current node alone
-                    return;
-                }
+                // Leave the current node alone
+                return;
             }
 
             // Copies the Node
@@ -186,12 +237,493 @@ pub(super) fn merge<'schematic>(
                 node.content_id = *new_content_id;
             }
 
+            if let Some(probability) = probability
+                && node.spawn_probability != SpawnProbability::Never.into()
+            {
+                node.spawn_probability = probability.into();
+            }
+
             target_node.assign_elem(node);
         });
 
+    Ok(source_content_map)
+}
+
+pub(super) fn stamp(
+    source: &Schematic,
+    destination: &mut Schematic,
+    at: MapVector,
+) -> Result<(), Error> {
+    let stamp_end = at
+        .checked_add(source.dimensions)
+        .ok_or(Error::OutOfBounds)?;
+    let current_dimensions = destination.dimensions;
+    let new_dimensions = MapVector {
+        x: current_dimensions.x.max(stamp_end.x),
+        y: current_dimensions.y.max(stamp_end.y),
+        z: current_dimensions.z.max(stamp_end.z),
+    };
+
+    if new_dimensions != current_dimensions {
+        let air = destination.register_content("air".into());
+        let mut grown_nodes =
+            Array3::from_elem(new_dimensions.as_shape(), RawNode::with_content_id(air));
+
+        let (z, y, x) = current_dimensions.as_shape();
+        grown_nodes
+            .slice_mut(s![..z, ..y, ..x])
+            .assign(&destination.nodes);
+
+        destination.nodes = grown_nodes;
+        destination
+            .layer_probabilities
+            .resize(new_dimensions.y as usize, SpawnProbability::Always);
+        destination.dimensions = new_dimensions;
+    }
+
+    merge(source, destination, at)
+}
+
+pub(super) fn resize(
+    schematic: &mut Schematic,
+    new_dimensions: MapVector,
+    fill_with_node: &Node,
+) -> Result<(), Error> {
+    if new_dimensions.volume() == 0 {
+        return Err(Error::EmptyDimension);
+    }
+    let current_dimensions = schematic.dimensions;
+    if new_dimensions == current_dimensions {
+        return Ok(());
+    }
+
+    // Only register the fill content when some space is actually added
+    let is_growing = new_dimensions.x > current_dimensions.x
+        || new_dimensions.y > current_dimensions.y
+        || new_dimensions.z > current_dimensions.z;
+    let fill_with_raw_node = if is_growing {
+        schematic.convert_node_to_raw_node(fill_with_node)
+    } else {
+        RawNode::default()
+    };
+
+    let mut resized_nodes = Array3::from_elem(new_dimensions.as_shape(), fill_with_raw_node);
+    let (z, y, x) = MapVector {
+        x: current_dimensions.x.min(new_dimensions.x),
+        y: current_dimensions.y.min(new_dimensions.y),
+        z: current_dimensions.z.min(new_dimensions.z),
+    }
+    .as_shape();
+    resized_nodes
+        .slice_mut(s![..z, ..y, ..x])
+        .assign(&schematic.nodes.slice(s![..z, ..y, ..x]));
+
+    schematic.nodes = resized_nodes;
+    schematic
+        .layer_probabilities
+        .resize(new_dimensions.y as usize, SpawnProbability::Always);
+    schematic.dimensions = new_dimensions;
+
+    Ok(())
+}
+
+/// Whether merging `merge_node` on top of `target_node` replaces the target node. `content_air`
+/// and `content_ignore` are the content IDs of "air" and "ignore" in the destination.
+fn is_placed_by_merge(
+    merge_node: &RawNode,
+    target_node: &RawNode,
+    content_air: Option<u16>,
+    content_ignore: Option<u16>,
+) -> bool {
+    if merge_node.spawn_probability == SpawnProbability::Never.into() && !merge_node.force_placement
+    {
+        // Such nodes are only placed where there is "nothing" yet
+        let content_id = Some(target_node.content_id);
+
+        content_id == content_air || content_id == content_ignore
+    } else {
+        true
+    }
+}
+
+pub(super) fn merge_preview(
+    source: &Schematic,
+    destination: &Schematic,
+    merge_at: MapVector,
+) -> Result<Vec<MapVector>, Error> {
+    let merge_end = merge_at
+        .checked_add(source.dimensions)
+        .ok_or(Error::OutOfBounds)?;
+    if !merge_end.fits_within(destination.dimensions) {
+        return Err(Error::OutOfBounds);
+    }
+
+    let mut changed = Vec::new();
+    for ((z, y, x), merge_node) in source.nodes.indexed_iter() {
+        let coordinates = MapVector {
+            x: merge_at.x + x as u16,
+            y: merge_at.y + y as u16,
+            z: merge_at.z + z as u16,
+        };
+        let target_node = &destination.nodes[coordinates.as_shape()];
+
+        if !is_placed_by_merge(
+            merge_node,
+            target_node,
+            destination.air_id,
+            destination.ignore_id,
+        ) {
+            continue;
+        }
+
+        let is_same_node = source.content_name_for_id(merge_node.content_id)
+            == destination.content_name_for_id(target_node.content_id)
+            && merge_node.spawn_probability == target_node.spawn_probability
+            && merge_node.force_placement == target_node.force_placement
+            && merge_node.param2 == target_node.param2;
+        if !is_same_node {
+            changed.push(coordinates);
+        }
+    }
+
+    Ok(changed)
+}
+
+#[cfg(feature = "rand")]
+pub(super) fn fill_weighted<R: rand::Rng>(
+    schematic: &mut Schematic,
+    from_position: MapVector,
+    fill_space: MapVector,
+    weights: &[(std::borrow::Cow<str>, f64)],
+    rng: &mut R,
+) -> Result<(), Error> {
+    use rand::distr::Distribution;
+    use rand::distr::weighted::WeightedIndex;
+
+    if weights.is_empty()
+        || weights
+            .iter()
+            .any(|(_content_name, weight)| !weight.is_finite() || *weight <= 0.0)
+    {
+        return Err(Error::InvalidWeights);
+    }
+    // Check the bounds before registering any content
+    schematic.region(from_position, fill_space)?;
+
+    let nodes: Vec<RawNode> = weights
+        .iter()
+        .map(|(content_name, _weight)| {
+            RawNode::with_content_id(schematic.register_content(content_name.clone()))
+        })
+        .collect();
+    let distribution = WeightedIndex::new(weights.iter().map(|(_content_name, weight)| *weight))
+        .map_err(|_| Error::InvalidWeights)?;
+
+    for node in schematic.region_mut(from_position, fill_space)? {
+        *node = nodes[distribution.sample(rng)];
+    }
+
+    Ok(())
+}
+
+pub(super) fn copy_region(
+    source: &Schematic,
+    source_from: MapVector,
+    size: MapVector,
+    destination: &mut Schematic,
+    destination_from: MapVector,
+) -> Result<(), Error> {
+    let source_to = source_from.checked_add(size).ok_or(Error::OutOfBounds)?;
+    let destination_to = destination_from
+        .checked_add(size)
+        .ok_or(Error::OutOfBounds)?;
+    if !source_to.fits_within(source.dimensions)
+        || !destination_to.fits_within(destination.dimensions)
+    {
+        return Err(Error::OutOfBounds);
+    }
+
+    let source_region = source.nodes.slice(s![
+        source_from.z as usize..source_to.z as usize,
+        source_from.y as usize..source_to.y as usize,
+        source_from.x as usize..source_to.x as usize
+    ]);
+
+    // Only register the content that's actually used in the copied region, and keep track of the
+    // content IDs it got in the destination
+    let mut content_map: HashMap<u16, u16> = HashMap::new();
+    for node in &source_region {
+        if let Entry::Vacant(entry) = content_map.entry(node.content_id) {
+            let content_name = source
+                .content_name_for_id(node.content_id)
+                .ok_or(Error::InvalidContentIndex(node.content_id))?;
+            entry.insert(destination.register_content(content_name.into()));
+        }
+    }
+
+    let destination_region = destination.nodes.slice_mut(s![
+        destination_from.z as usize..destination_to.z as usize,
+        destination_from.y as usize..destination_to.y as usize,
+        destination_from.x as usize..destination_to.x as usize
+    ]);
+    Zip::from(&source_region)
+        .and(destination_region)
+        .for_each(|source_node, destination_node| {
+            *destination_node = RawNode {
+                content_id: content_map[&source_node.content_id],
+                ..*source_node
+            };
+        });
+
+    Ok(())
+}
+
+pub(super) fn erode(
+    schematic: &mut Schematic,
+    target: &str,
+    into: &str,
+    min_same_neighbors: u8,
+) -> Result<usize, Error> {
+    let target_id = schematic
+        .content_id_for_name(target)
+        .ok_or_else(|| Error::InvalidContentName(target.to_string()))?;
+    let into_node = RawNode::with_content_id(schematic.register_content(into.into()));
+
+    // Find all nodes to erode before changing any of them, so the outcome doesn't depend on the
+    // order in which the nodes are visited
+    let eroded_nodes: Vec<MapVector> = schematic
+        .nodes
+        .indexed_iter()
+        .filter(|(_index, node)| node.content_id == target_id)
+        .map(|((z, y, x), _node)| MapVector {
+            x: x as u16,
+            y: y as u16,
+            z: z as u16,
+        })
+        .filter(|coordinates| {
+            let same_neighbors = schematic
+                .neighbor_coordinates(*coordinates)
+                .filter(|neighbor| schematic.nodes[neighbor.as_shape()].content_id == target_id)
+                .count();
+
+            same_neighbors < min_same_neighbors as usize
+        })
+        .collect();
+
+    for coordinates in &eroded_nodes {
+        schematic.nodes[coordinates.as_shape()] = into_node;
+    }
+
+    Ok(eroded_nodes.len())
+}
+
+pub(super) fn replace_matching<F: Fn(&Node) -> bool>(
+    schematic: &mut Schematic,
+    predicate: F,
+    replacement: &Node,
+) -> Result<usize, Error> {
+    // Gather the nodes first, as the predicate borrows the content names of the schematic
+    let mut matching_nodes = Vec::new();
+    for (index, raw_node) in schematic.nodes.indexed_iter() {
+        if predicate(&raw_node.to_node(schematic)?) {
+            matching_nodes.push(index);
+        }
+    }
+
+    if matching_nodes.is_empty() {
+        return Ok(0);
+    }
+
+    let replacement_node = RawNode::new(
+        schematic.register_content(replacement.content_name.clone()),
+        replacement.spawn_probability,
+        replacement.force_placement,
+        replacement.param2,
+    );
+    for index in &matching_nodes {
+        schematic.nodes[*index] = replacement_node;
+    }
+
+    Ok(matching_nodes.len())
+}
+
+pub(super) fn shift(
+    schematic: &mut Schematic,
+    offset: (i32, i32, i32),
+    wrap: bool,
+) -> Result<(), Error> {
+    let (size_z, size_y, size_x) = schematic.dimensions.as_shape();
+    // Where a coordinate along an axis of `size` ends up, if it stays within the Schematic
+    let shift_coordinate = |coordinate: usize, offset: i32, size: usize| {
+        let shifted = coordinate as i64 + offset as i64;
+        if wrap {
+            Some(shifted.rem_euclid(size as i64) as usize)
+        } else {
+            usize::try_from(shifted)
+                .ok()
+                .filter(|shifted| *shifted < size)
+        }
+    };
+
+    // When wrapping, every position gets overwritten, so only fill with air when nodes are dropped
+    let mut shifted_nodes = if wrap {
+        schematic.nodes.clone()
+    } else {
+        let air = RawNode::with_content_id(schematic.register_content("air".into()));
+        Array3::from_elem(schematic.nodes.raw_dim(), air)
+    };
+    for ((z, y, x), node) in schematic.nodes.indexed_iter() {
+        if let (Some(x), Some(y), Some(z)) = (
+            shift_coordinate(x, offset.0, size_x),
+            shift_coordinate(y, offset.1, size_y),
+            shift_coordinate(z, offset.2, size_z),
+        ) {
+            shifted_nodes[(z, y, x)] = *node;
+        }
+    }
+    schematic.nodes = shifted_nodes;
+
+    // The layer probabilities move along with the nodes
+    let mut shifted_layer_probabilities = vec![SpawnProbability::Always; size_y];
+    for (y, probability) in schematic.layer_probabilities.iter().enumerate() {
+        if let Some(y) = shift_coordinate(y, offset.1, size_y) {
+            shifted_layer_probabilities[y] = *probability;
+        }
+    }
+    schematic.layer_probabilities = shifted_layer_probabilities;
+
     Ok(())
 }
 
+pub(super) fn merge_clipped(
+    source: &Schematic,
+    destination: &mut Schematic,
+    merge_at: MapVector,
+) -> Result<(), Error> {
+    if merge_at.x >= destination.dimensions.x
+        || merge_at.y >= destination.dimensions.y
+        || merge_at.z >= destination.dimensions.z
+    {
+        return Err(Error::OutOfBounds);
+    }
+
+    let clipped_dimensions = MapVector {
+        x: source
+            .dimensions
+            .x
+            .min(destination.dimensions.x - merge_at.x),
+        y: source
+            .dimensions
+            .y
+            .min(destination.dimensions.y - merge_at.y),
+        z: source
+            .dimensions
+            .z
+            .min(destination.dimensions.z - merge_at.z),
+    };
+    let (z, y, x) = clipped_dimensions.as_shape();
+    let clipped_source = SchematicRef {
+        schematic: source,
+        nodes_view: source.nodes.slice(s![..z, ..y, ..x]),
+    };
+
+    merge(&clipped_source, destination, merge_at)
+}
+
+pub(super) fn downscale(schematic: &Schematic, factor: MapVector) -> Result<Schematic, Error> {
+    if factor.x == 0 || factor.y == 0 || factor.z == 0 {
+        return Err(Error::OutOfBounds);
+    }
+
+    let new_dimensions = MapVector::new(
+        schematic.dimensions.x / factor.x,
+        schematic.dimensions.y / factor.y,
+        schematic.dimensions.z / factor.z,
+    )?;
+    if new_dimensions.volume() == 0 {
+        return Err(Error::EmptyDimension);
+    }
+
+    let content_air = schematic.air_id;
+    let mut content_counts: HashMap<u16, usize> = HashMap::new();
+
+    // Like `split_into_chunks()`, `exact_chunks()` drops any nodes that don't fit in a whole block
+    let downscaled_nodes =
+        Zip::from(schematic.nodes.exact_chunks(factor.as_shape())).map_collect(|block| {
+            content_counts.clear();
+            for node in block
+                .iter()
+                .filter(|node| Some(node.content_id) != content_air)
+            {
+                *content_counts.entry(node.content_id).or_default() += 1;
+            }
+
+            // Ties are broken by picking the lowest content ID
+            let majority_content_id = content_counts
+                .iter()
+                .max_by(|(id_a, count_a), (id_b, count_b)| {
+                    count_a.cmp(count_b).then(id_b.cmp(id_a))
+                })
+                .map(|(content_id, _count)| *content_id);
+
+            // Copy the first node of the winning content, so its other properties (e.g. param2)
+            // are kept. A block that only contains air becomes air.
+            match majority_content_id {
+                Some(content_id) => *block
+                    .iter()
+                    .find(|node| node.content_id == content_id)
+                    .expect("block to contain a node with the counted content"),
+                None => *block.first().expect("block to contain at least one node"),
+            }
+        });
+
+    let mut downscaled_schematic = Schematic::with_array3(new_dimensions, downscaled_nodes);
+    downscaled_schematic.set_content_names(schematic.content_names.clone());
+    downscaled_schematic.name.clone_from(&schematic.name);
+
+    Ok(downscaled_schematic)
+}
+
+pub(super) fn upscale(schematic: &Schematic, factor: MapVector) -> Result<Schematic, Error> {
+    let new_dimensions = MapVector::new(
+        schematic
+            .dimensions
+            .x
+            .checked_mul(factor.x)
+            .ok_or(Error::OutOfBounds)?,
+        schematic
+            .dimensions
+            .y
+            .checked_mul(factor.y)
+            .ok_or(Error::OutOfBounds)?,
+        schematic
+            .dimensions
+            .z
+            .checked_mul(factor.z)
+            .ok_or(Error::OutOfBounds)?,
+    )?;
+    if new_dimensions.volume() == 0 {
+        return Err(Error::EmptyDimension);
+    }
+
+    let (factor_z, factor_y, factor_x) = factor.as_shape();
+    let upscaled_nodes = Array3::from_shape_fn(new_dimensions.as_shape(), |(z, y, x)| {
+        schematic.nodes[(z / factor_z, y / factor_y, x / factor_x)]
+    });
+
+    let mut upscaled_schematic = Schematic::with_array3(new_dimensions, upscaled_nodes);
+    upscaled_schematic.set_content_names(schematic.content_names.clone());
+    upscaled_schematic.name.clone_from(&schematic.name);
+    // Each layer is repeated `factor.y` times, and so is its probability
+    upscaled_schematic.layer_probabilities = schematic
+        .layer_probabilities
+        .iter()
+        .flat_map(|probability| std::iter::repeat_n(*probability, factor_y))
+        .collect();
+
+    Ok(upscaled_schematic)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -225,6 +757,81 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_fill_raw() {
+        let mut schematic = Schematic::new((3, 3, 3).try_into().unwrap()).unwrap();
+        let content_id = schematic.register_content("default:dirt".into());
+
+        schematic
+            .fill_raw(
+                (1, 1, 1).try_into().unwrap(),
+                (2, 2, 2).try_into().unwrap(),
+                RawNode::with_content_id(content_id),
+            )
+            .unwrap();
+
+        assert_eq!(schematic.content_names, &["air", "default:dirt"]);
+        assert!(
+            schematic
+                .nodes
+                .slice(s![1.., 1.., 1..])
+                .iter()
+                .all(|node| node.content_id == content_id)
+        );
+        assert_eq!(
+            schematic
+                .nodes
+                .iter()
+                .filter(|node| node.content_id == content_id)
+                .count(),
+            8
+        );
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_fill_weighted() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let mut schematic = Schematic::new((20, 20, 20).try_into().unwrap()).unwrap();
+        let mut rng = StdRng::seed_from_u64(42);
+        let weights = [("default:dirt".into(), 3.0), ("default:stone".into(), 1.0)];
+
+        schematic
+            .fill_weighted(
+                (0, 0, 0).try_into().unwrap(),
+                (20, 10, 20).try_into().unwrap(),
+                &weights,
+                &mut rng,
+            )
+            .unwrap();
+
+        let count = |content_name| {
+            let content_id = schematic.content_id_for_name(content_name).unwrap();
+            schematic
+                .nodes
+                .iter()
+                .filter(|node| node.content_id == content_id)
+                .count()
+        };
+        // 4000 nodes were filled, 3000 of which are expected to be dirt
+        assert_eq!(count("air"), 4000);
+        assert!((2850..=3150).contains(&count("default:dirt")));
+        assert_eq!(count("default:dirt") + count("default:stone"), 4000);
+
+        let invalid_weights = [("default:dirt".into(), 3.0), ("default:stone".into(), 0.0)];
+        assert!(matches!(
+            schematic.fill_weighted(
+                (0, 0, 0).try_into().unwrap(),
+                (1, 1, 1).try_into().unwrap(),
+                &invalid_weights,
+                &mut rng,
+            ),
+            Err(Error::InvalidWeights)
+        ));
+    }
+
     #[test]
     fn test_fill_out_of_bounds() {
         let mut schematic = Schematic::new((2, 2, 2).try_into().unwrap()).unwrap();
@@ -239,6 +846,39 @@ mod tests {
             .unwrap_err();
     }
 
+    #[test]
+    fn test_out_of_bounds_along_one_axis() {
+        // Sticking out along Y only, while X is smaller than the destination
+        let mut schematic = Schematic::new((2, 2, 2).try_into().unwrap()).unwrap();
+        let source = Schematic::new((1, 5, 1).try_into().unwrap()).unwrap();
+        let node = Node::with_content_name("default:dirt".into());
+
+        assert!(matches!(
+            schematic.merge_preview(&source, MapVector::ZERO),
+            Err(Error::OutOfBounds)
+        ));
+        assert!(matches!(
+            schematic.merge(&source, MapVector::ZERO),
+            Err(Error::OutOfBounds)
+        ));
+        assert!(matches!(
+            schematic.merge_returning_map(&source, MapVector::ZERO),
+            Err(Error::OutOfBounds)
+        ));
+        assert!(matches!(
+            schematic.record_changes().merge(&source, MapVector::ZERO),
+            Err(Error::OutOfBounds)
+        ));
+        assert!(matches!(
+            schematic.fill(MapVector::ZERO, (1, 3, 1).try_into().unwrap(), &node),
+            Err(Error::OutOfBounds)
+        ));
+        assert!(matches!(
+            schematic.place_node(&node, (0, 2, 0).try_into().unwrap()),
+            Err(Error::OutOfBounds)
+        ));
+    }
+
     #[test]
     fn test_dimensions_checked_add() {
         let dimensions = MapVector::new(1000, 1000, 1000).unwrap();
@@ -278,6 +918,30 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case::existing_content("default:dirt")]
+    #[case::air("air")]
+    fn test_insert_layer_with_registered_content(#[case] content_name: &str) {
+        let mut original_schematic = Schematic::new((2, 1, 2).try_into().unwrap()).unwrap();
+        original_schematic.register_content("default:dirt".into());
+        let node = Node::with_content_name(content_name.into());
+
+        let new_schematic = original_schematic.insert_layer(1, &node).unwrap();
+
+        assert_eq!(
+            new_schematic.content_names().collect::<Vec<_>>(),
+            ["air", "default:dirt"]
+        );
+        assert_eq!(
+            new_schematic.node_at((0, 1, 0).try_into().unwrap()),
+            Some(node)
+        );
+        assert_eq!(
+            Schematic::from_bytes(new_schematic.to_bytes()).unwrap(),
+            new_schematic
+        );
+    }
+
     #[test]
     fn test_merge() {
         let mut schematic_1 = Schematic::new((3, 3, 3).try_into().unwrap()).unwrap();
@@ -323,10 +987,263 @@ mod tests {
     }
 
     #[test]
-    fn test_merge_small_schematic_into_larger() {
-        let mut schematic_1 = Schematic::new((8, 8, 8).try_into().unwrap()).unwrap();
-        schematic_1.register_content("something".into());
-
+    fn test_merge_ignore_source_air() {
+        let mut target = Schematic::new((3, 1, 3).try_into().unwrap()).unwrap();
+        target
+            .fill(
+                (0, 0, 0).try_into().unwrap(),
+                target.dimensions,
+                &Node::with_content_name("default:dirt".into()),
+            )
+            .unwrap();
+
+        let mut plus = Schematic::new((3, 1, 3).try_into().unwrap()).unwrap();
+        let stone = Node::with_content_name("default:stone".into());
+        for (x, z) in [(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)] {
+            plus.place_node(&stone, (x, 0, z).try_into().unwrap())
+                .unwrap();
+        }
+
+        let mut merged = target.clone();
+        merged
+            .merge_ignore_source_air(&plus, (0, 0, 0).try_into().unwrap())
+            .unwrap();
+
+        let content_name_at = |schematic: &Schematic, x, z| {
+            schematic
+                .node_at((x, 0, z).try_into().unwrap())
+                .unwrap()
+                .content_name
+                .into_owned()
+        };
+        for (x, z) in [(0, 0), (2, 0), (0, 2), (2, 2)] {
+            assert_eq!(content_name_at(&merged, x, z), "default:dirt");
+        }
+        for (x, z) in [(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)] {
+            assert_eq!(content_name_at(&merged, x, z), "default:stone");
+        }
+
+        // A regular merge does overwrite the target with air
+        target.merge(&plus, (0, 0, 0).try_into().unwrap()).unwrap();
+        assert_eq!(content_name_at(&target, 0, 0), "air");
+    }
+
+    #[test]
+    fn test_merge_returning_map() {
+        let mut destination = Schematic::new((3, 3, 3).try_into().unwrap()).unwrap();
+        destination.register_content("default:stone".into());
+        destination.register_content("default:dirt".into());
+
+        let mut source = Schematic::new((2, 2, 2).try_into().unwrap()).unwrap();
+        source.register_content("default:dirt".into());
+        source.register_content("default:mese".into());
+
+        let content_map = destination
+            .merge_returning_map(&source, (0, 0, 0).try_into().unwrap())
+            .unwrap();
+
+        assert_eq!(content_map, HashMap::from([(0, 0), (1, 2), (2, 3)]));
+        assert_eq!(destination.content_id_for_name("default:mese"), Some(3));
+    }
+
+    #[test]
+    fn test_resize() {
+        let mut schematic = Schematic::new((2, 2, 2).try_into().unwrap()).unwrap();
+        let stone = Node::with_content_name("default:stone".into());
+        schematic
+            .fill((0, 0, 0).try_into().unwrap(), schematic.dimensions, &stone)
+            .unwrap();
+        let stone_id = schematic.content_id_for_name("default:stone").unwrap();
+
+        schematic
+            .resize(
+                MapVector::splat(3).unwrap(),
+                &Node::with_content_name("default:dirt".into()),
+            )
+            .unwrap();
+
+        assert!(schematic.validate().is_ok());
+        assert_eq!(schematic.layer_probabilities().len(), 3);
+        let dirt_id = schematic.content_id_for_name("default:dirt").unwrap();
+        for ((z, y, x), node) in schematic.nodes.indexed_iter() {
+            let expected_id = if x < 2 && y < 2 && z < 2 {
+                stone_id
+            } else {
+                dirt_id
+            };
+            assert_eq!(node.content_id, expected_id);
+        }
+
+        schematic
+            .resize((2, 1, 2).try_into().unwrap(), &stone)
+            .unwrap();
+
+        assert!(schematic.validate().is_ok());
+        assert_eq!(schematic.layer_probabilities().len(), 1);
+        assert!(
+            schematic
+                .nodes
+                .iter()
+                .all(|node| node.content_id == stone_id)
+        );
+
+        schematic
+            .resize((2, 0, 2).try_into().unwrap(), &stone)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_merge_with_probability() {
+        let mut schematic_1 = Schematic::new((3, 3, 3).try_into().unwrap()).unwrap();
+
+        let mut schematic_2 = Schematic::new((2, 2, 2).try_into().unwrap()).unwrap();
+        schematic_2
+            .fill(
+                (0, 0, 0).try_into().unwrap(),
+                schematic_2.dimensions,
+                &Node::with_content_name("default:leaves".into()),
+            )
+            .unwrap();
+
+        schematic_1
+            .merge_with_probability(
+                &schematic_2,
+                (1, 1, 1).try_into().unwrap(),
+                SpawnProbability::Custom(50),
+            )
+            .unwrap();
+
+        let default_leaves = schematic_1.content_id_for_name("default:leaves").unwrap();
+        let (placed, untouched): (Vec<&RawNode>, Vec<&RawNode>) = schematic_1
+            .nodes
+            .iter()
+            .partition(|node| node.content_id == default_leaves);
+
+        assert_eq!(placed.len(), 8);
+        assert!(
+            placed
+                .iter()
+                .all(|node| node.spawn_probability == SpawnProbability::Custom(50).into())
+        );
+        assert!(
+            untouched
+                .iter()
+                .all(|node| node.spawn_probability == SpawnProbability::Always.into())
+        );
+    }
+
+    #[test]
+    fn test_merge_preview() {
+        // The bottom layer of the target is solid, the top layer is air
+        let mut target = Schematic::new((2, 2, 1).try_into().unwrap()).unwrap();
+        target
+            .fill(
+                (0, 0, 0).try_into().unwrap(),
+                (2, 1, 1).try_into().unwrap(),
+                &Node::with_content_name("default:stone".into()),
+            )
+            .unwrap();
+
+        // Optional nodes everywhere, except for a stone node that's already in the target
+        let mut source = Schematic::new((2, 2, 1).try_into().unwrap()).unwrap();
+        source
+            .fill(
+                (0, 0, 0).try_into().unwrap(),
+                source.dimensions,
+                &Node::new("default:dirt".into(), SpawnProbability::Never, false, 0),
+            )
+            .unwrap();
+        source
+            .place_node(
+                &Node::with_content_name("default:stone".into()),
+                (1, 0, 0).try_into().unwrap(),
+            )
+            .unwrap();
+
+        let original_target = target.clone();
+        let preview = target
+            .merge_preview(&source, (0, 0, 0).try_into().unwrap())
+            .unwrap();
+
+        assert_eq!(target, original_target);
+        assert_eq!(
+            preview,
+            &[
+                MapVector::new(0, 1, 0).unwrap(),
+                MapVector::new(1, 1, 0).unwrap(),
+            ]
+        );
+
+        target
+            .merge(&source, (0, 0, 0).try_into().unwrap())
+            .unwrap();
+        let changed: Vec<MapVector> = target
+            .nodes
+            .indexed_iter()
+            .filter(|(index, node)| *node != &original_target.nodes[*index])
+            .map(|((z, y, x), _node)| MapVector::new(x as u16, y as u16, z as u16).unwrap())
+            .collect();
+        assert_eq!(preview, changed);
+    }
+
+    #[test]
+    fn test_stamp() {
+        let mut target = Schematic::new((3, 3, 3).try_into().unwrap()).unwrap();
+        target
+            .place_node(
+                &Node::with_content_name("default:stone".into()),
+                (2, 2, 2).try_into().unwrap(),
+            )
+            .unwrap();
+
+        let mut source = Schematic::new((2, 2, 2).try_into().unwrap()).unwrap();
+        source
+            .fill(
+                (0, 0, 0).try_into().unwrap(),
+                source.dimensions,
+                &Node::with_content_name("default:wood".into()),
+            )
+            .unwrap();
+
+        // Fits, so nothing grows
+        target
+            .stamp(&source, (0, 0, 0).try_into().unwrap())
+            .unwrap();
+        assert_eq!(target.dimensions, (3, 3, 3).try_into().unwrap());
+
+        target
+            .stamp(&source, (2, 0, 1).try_into().unwrap())
+            .unwrap();
+
+        assert_eq!(target.dimensions, (4, 3, 3).try_into().unwrap());
+        assert_eq!(target.layer_probabilities.len(), 3);
+        assert!(target.validate().is_ok());
+
+        let default_wood = target.content_id_for_name("default:wood").unwrap();
+        let default_stone = target.content_id_for_name("default:stone").unwrap();
+        let content_at = |x, y, z| target.nodes[[z, y, x]].content_id;
+        // The original nodes stay in place, the new space is air apart from the stamp
+        assert_eq!(content_at(0, 0, 0), default_wood);
+        assert_eq!(content_at(2, 2, 2), default_stone);
+        assert_eq!(content_at(3, 0, 0), target.air_id.unwrap());
+        assert_eq!(content_at(3, 2, 2), target.air_id.unwrap());
+        for (x, y, z) in [(2, 0, 1), (3, 1, 2), (3, 0, 1)] {
+            assert_eq!(content_at(x, y, z), default_wood);
+        }
+
+        target
+            .stamp(&source, (1, 2, 0).try_into().unwrap())
+            .unwrap();
+        assert_eq!(target.dimensions, (4, 4, 3).try_into().unwrap());
+        assert_eq!(target.layer_probabilities.len(), 4);
+        assert!(target.validate().is_ok());
+    }
+
+    #[test]
+    fn test_merge_small_schematic_into_larger() {
+        let mut schematic_1 = Schematic::new((8, 8, 8).try_into().unwrap()).unwrap();
+        schematic_1.register_content("something".into());
+
         let mut schematic_2 = Schematic::new((2, 2, 2).try_into().unwrap()).unwrap();
         schematic_2
             .fill(
@@ -347,6 +1264,296 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_copy_region() {
+        let mut source = Schematic::new((4, 4, 4).try_into().unwrap()).unwrap();
+        source
+            .place_node(
+                &Node::with_content_name("default:torch".into()),
+                (3, 0, 0).try_into().unwrap(),
+            )
+            .unwrap();
+        source
+            .fill(
+                (0, 1, 1).try_into().unwrap(),
+                (2, 2, 2).try_into().unwrap(),
+                &Node::with_content_name("default:stone".into()),
+            )
+            .unwrap();
+        source.register_content("default:unused".into());
+
+        let mut destination = Schematic::new((3, 3, 3).try_into().unwrap()).unwrap();
+        destination.register_content("default:stone".into());
+        destination.register_content("default:dirt".into());
+
+        // A single node
+        destination
+            .copy_region(
+                &source,
+                (3, 0, 0).try_into().unwrap(),
+                (1, 1, 1).try_into().unwrap(),
+                (0, 0, 2).try_into().unwrap(),
+            )
+            .unwrap();
+        // A box, including some air around the stone
+        destination
+            .copy_region(
+                &source,
+                (0, 0, 1).try_into().unwrap(),
+                (2, 3, 2).try_into().unwrap(),
+                (1, 0, 0).try_into().unwrap(),
+            )
+            .unwrap();
+
+        destination.validate().unwrap();
+        assert_eq!(
+            destination.content_names,
+            &["air", "default:stone", "default:dirt", "default:torch"]
+        );
+        assert_eq!(destination.nodes[(2, 0, 0)].content_id, 3);
+        assert_eq!(
+            destination
+                .nodes
+                .slice(s![0..2, 1..3, 1..3])
+                .iter()
+                .filter(|node| node.content_id == 1)
+                .count(),
+            8
+        );
+        assert_eq!(destination.nodes[(0, 0, 1)].content_id, 0);
+
+        destination
+            .copy_region(
+                &source,
+                (3, 0, 0).try_into().unwrap(),
+                (2, 1, 1).try_into().unwrap(),
+                (0, 0, 0).try_into().unwrap(),
+            )
+            .unwrap_err();
+        destination
+            .copy_region(
+                &source,
+                (0, 0, 0).try_into().unwrap(),
+                (2, 2, 2).try_into().unwrap(),
+                (2, 0, 0).try_into().unwrap(),
+            )
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_replace_matching() {
+        let mut schematic = Schematic::new((4, 1, 1).try_into().unwrap()).unwrap();
+        for (x, param2) in [0, 3, 1, 3].into_iter().enumerate() {
+            let stair = Node::new(
+                "stairs:stair_wood".into(),
+                SpawnProbability::Always,
+                false,
+                param2,
+            );
+            schematic
+                .place_node(&stair, (x as u16, 0, 0).try_into().unwrap())
+                .unwrap();
+        }
+        let replacement = Node::new(
+            "stairs:stair_stone".into(),
+            SpawnProbability::Always,
+            false,
+            3,
+        );
+
+        let num_replaced = schematic
+            .replace_matching(
+                |node| node.content_name == "stairs:stair_wood" && node.param2 == 3,
+                &replacement,
+            )
+            .unwrap();
+
+        assert_eq!(num_replaced, 2);
+        let content_names: Vec<String> = schematic
+            .annotated_nodes()
+            .map(|annotated_node| annotated_node.node.content_name.into_owned())
+            .collect();
+        assert_eq!(
+            content_names,
+            [
+                "stairs:stair_wood",
+                "stairs:stair_stone",
+                "stairs:stair_wood",
+                "stairs:stair_stone"
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case::forward((1, 2, -1), false, Some((2, 2, 0)))]
+    #[case::dropped((2, 0, 0), false, None)]
+    #[case::wrapped((2, 0, 0), true, Some((0, 0, 1)))]
+    #[case::wrapped_backwards((-2, -1, -4), true, Some((2, 2, 0)))]
+    fn test_shift(
+        #[case] offset: (i32, i32, i32),
+        #[case] wrap: bool,
+        #[case] expected_position: Option<(u16, u16, u16)>,
+    ) {
+        let mut schematic = Schematic::new((3, 3, 3).try_into().unwrap()).unwrap();
+        let marker = Node::with_content_name("default:mese".into());
+        schematic
+            .place_node(&marker, (1, 0, 1).try_into().unwrap())
+            .unwrap();
+        let marker_id = schematic.content_id_for_name("default:mese").unwrap();
+
+        schematic.shift(offset, wrap).unwrap();
+
+        let marker_positions: Vec<MapVector> = schematic
+            .nodes
+            .indexed_iter()
+            .filter(|(_index, node)| node.content_id == marker_id)
+            .map(|(index, _node)| MapVector::from_shape(index).unwrap())
+            .collect();
+        let expected_positions: Vec<MapVector> = expected_position
+            .map(|position| position.try_into().unwrap())
+            .into_iter()
+            .collect();
+        assert_eq!(marker_positions, expected_positions);
+        assert_eq!(schematic.num_nodes(), 27);
+    }
+
+    #[test]
+    fn test_erode() {
+        let mut schematic = Schematic::new((5, 5, 5).try_into().unwrap()).unwrap();
+        let stone = Node::with_content_name("default:stone".into());
+        // A solid block
+        schematic
+            .fill(
+                (0, 0, 0).try_into().unwrap(),
+                (3, 3, 3).try_into().unwrap(),
+                &stone,
+            )
+            .unwrap();
+        // A thin spike
+        schematic
+            .fill(
+                (4, 0, 4).try_into().unwrap(),
+                (1, 5, 1).try_into().unwrap(),
+                &stone,
+            )
+            .unwrap();
+
+        let num_eroded = schematic.erode("default:stone", "air", 3).unwrap();
+
+        assert_eq!(num_eroded, 5);
+        let stone_id = schematic.content_id_for_name("default:stone").unwrap();
+        assert!(
+            schematic
+                .nodes
+                .slice(s![0..3, 0..3, 0..3])
+                .iter()
+                .all(|node| node.content_id == stone_id)
+        );
+        assert!(
+            schematic
+                .nodes
+                .slice(s![4, .., 4])
+                .iter()
+                .all(|node| node.content_id == 0)
+        );
+
+        schematic.erode("default:cobble", "air", 3).unwrap_err();
+    }
+
+    #[test]
+    fn test_merge_rotated() {
+        let mut source = Schematic::new((2, 1, 1).try_into().unwrap()).unwrap();
+        source
+            .place_node(
+                &Node::with_content_name("default:dirt".into()),
+                (0, 0, 0).try_into().unwrap(),
+            )
+            .unwrap();
+        source
+            .place_node(
+                &Node::new("default:dry_dirt".into(), SpawnProbability::Never, false, 0),
+                (1, 0, 0).try_into().unwrap(),
+            )
+            .unwrap();
+
+        let mut destination = Schematic::new((3, 1, 1).try_into().unwrap()).unwrap();
+        destination
+            .place_node(
+                &Node::with_content_name("default:cobble".into()),
+                (0, 0, 0).try_into().unwrap(),
+            )
+            .unwrap();
+
+        destination
+            .merge(&source.rotate_180(), (0, 0, 0).try_into().unwrap())
+            .unwrap();
+
+        destination.validate().unwrap();
+        let content_name_at = |schematic: &Schematic, x| {
+            schematic
+                .node_at((x, 0, 0).try_into().unwrap())
+                .unwrap()
+                .content_name
+                .into_owned()
+        };
+        assert_eq!(
+            content_name_at(&destination, 0),
+            "default:cobble",
+            "The optional default:dry_dirt node should not have replaced the existing node"
+        );
+        assert_eq!(content_name_at(&destination, 1), "default:dirt");
+        assert_eq!(content_name_at(&destination, 2), "air");
+
+        // A rotation that swaps the X and Z dimensions
+        let mut destination = Schematic::new((1, 1, 2).try_into().unwrap()).unwrap();
+        destination
+            .merge(&source.rotate_left(), (0, 0, 0).try_into().unwrap())
+            .unwrap();
+        destination.validate().unwrap();
+    }
+
+    #[test]
+    fn test_merge_clipped() {
+        let mut schematic_1 = Schematic::new((4, 4, 4).try_into().unwrap()).unwrap();
+        let mut schematic_2 = Schematic::new((3, 3, 3).try_into().unwrap()).unwrap();
+        schematic_2
+            .fill(
+                (0, 0, 0).try_into().unwrap(),
+                schematic_2.dimensions,
+                &Node::with_content_name("default:dirt".into()),
+            )
+            .unwrap();
+
+        schematic_1
+            .merge(&schematic_2, (2, 2, 2).try_into().unwrap())
+            .unwrap_err();
+        schematic_1
+            .merge_clipped(&schematic_2, (2, 2, 2).try_into().unwrap())
+            .unwrap();
+
+        schematic_1.validate().unwrap();
+        let default_dirt = schematic_1.content_id_for_name("default:dirt").unwrap();
+        assert_eq!(
+            schematic_1
+                .nodes
+                .iter()
+                .filter(|node| node.content_id == default_dirt)
+                .count(),
+            8
+        );
+        assert!(
+            schematic_1
+                .nodes
+                .slice(s![2.., 2.., 2..])
+                .iter()
+                .all(|node| node.content_id == default_dirt)
+        );
+
+        schematic_1
+            .merge_clipped(&schematic_2, (4, 0, 0).try_into().unwrap())
+            .unwrap_err();
+    }
+
     #[rstest]
     fn test_merge_optional_node_doesnt_overwrite_existing(mut schematic: Schematic) {
         let content_id = schematic.register_content("default:dry_dirt".into());
@@ -366,6 +1573,147 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_downscale() {
+        let mut schematic = Schematic::new((4, 4, 4).try_into().unwrap()).unwrap();
+        let dirt = Node::with_content_name("default:dirt".into());
+        let stone = Node::with_content_name("default:stone".into());
+        let cobble = Node::with_content_name("default:cobble".into());
+
+        // Block (0, 0, 0): only dirt
+        schematic
+            .fill(
+                (0, 0, 0).try_into().unwrap(),
+                (2, 2, 2).try_into().unwrap(),
+                &dirt,
+            )
+            .unwrap();
+        // Block (1, 0, 0): 6 stone, 2 cobble
+        schematic
+            .fill(
+                (2, 0, 0).try_into().unwrap(),
+                (2, 2, 2).try_into().unwrap(),
+                &stone,
+            )
+            .unwrap();
+        schematic
+            .fill(
+                (2, 0, 0).try_into().unwrap(),
+                (1, 1, 2).try_into().unwrap(),
+                &cobble,
+            )
+            .unwrap();
+        // Block (0, 1, 0): 4 stone and 4 dirt, the tie goes to the lowest content ID (dirt)
+        schematic
+            .fill(
+                (0, 2, 0).try_into().unwrap(),
+                (2, 1, 2).try_into().unwrap(),
+                &stone,
+            )
+            .unwrap();
+        schematic
+            .fill(
+                (0, 3, 0).try_into().unwrap(),
+                (2, 1, 2).try_into().unwrap(),
+                &dirt,
+            )
+            .unwrap();
+        // Block (0, 0, 1): a single cobble node, the rest is air
+        schematic
+            .place_node(&cobble, (0, 0, 2).try_into().unwrap())
+            .unwrap();
+
+        let downscaled = schematic.downscale((2, 2, 2).try_into().unwrap()).unwrap();
+
+        assert_eq!(downscaled.dimensions, (2, 2, 2).try_into().unwrap());
+        downscaled.validate().unwrap();
+
+        let content_name_at = |x, y, z| {
+            downscaled
+                .node_at((x, y, z).try_into().unwrap())
+                .unwrap()
+                .content_name
+        };
+        assert_eq!(content_name_at(0, 0, 0), "default:dirt");
+        assert_eq!(content_name_at(1, 0, 0), "default:stone");
+        assert_eq!(content_name_at(0, 1, 0), "default:dirt");
+        assert_eq!(content_name_at(0, 0, 1), "default:cobble");
+        assert_eq!(content_name_at(1, 1, 1), "air");
+    }
+
+    #[test]
+    fn test_downscale_drops_remainder() {
+        let schematic = Schematic::new((5, 3, 4).try_into().unwrap()).unwrap();
+
+        let downscaled = schematic.downscale((2, 2, 2).try_into().unwrap()).unwrap();
+
+        assert_eq!(downscaled.dimensions, (2, 1, 2).try_into().unwrap());
+        schematic
+            .downscale((6, 1, 1).try_into().unwrap())
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_upscale() {
+        let mut schematic = Schematic::new((2, 1, 2).try_into().unwrap()).unwrap();
+        for (x, z, content_name) in [
+            (0, 0, "default:dirt"),
+            (1, 0, "default:stone"),
+            (0, 1, "default:cobble"),
+            (1, 1, "default:wood"),
+        ] {
+            schematic
+                .place_node(
+                    &Node::with_content_name(content_name.into()),
+                    (x, 0, z).try_into().unwrap(),
+                )
+                .unwrap();
+        }
+
+        let upscaled = schematic.upscale((2, 1, 2).try_into().unwrap()).unwrap();
+
+        assert_eq!(upscaled.dimensions, (4, 1, 4).try_into().unwrap());
+        upscaled.validate().unwrap();
+        for x in 0..4 {
+            for z in 0..4 {
+                let upscaled_node = upscaled.node_at((x, 0, z).try_into().unwrap()).unwrap();
+                let original_node = schematic
+                    .node_at((x / 2, 0, z / 2).try_into().unwrap())
+                    .unwrap();
+
+                assert_eq!(upscaled_node, original_node);
+            }
+        }
+    }
+
+    #[test]
+    fn test_upscale_too_big() {
+        let schematic = Schematic::new((2, 1, 2).try_into().unwrap()).unwrap();
+
+        schematic
+            .upscale((40000, 1, 1).try_into().unwrap())
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_merge_optional_node_overwrites_air() {
+        let mut schematic = Schematic::new((2, 2, 2).try_into().unwrap()).unwrap();
+        schematic.register_content("default:cobble".into());
+        let mut optional_schematic = Schematic::new((1, 1, 1).try_into().unwrap()).unwrap();
+        let content_id = optional_schematic.register_content("default:dry_dirt".into());
+        let mut optional_node = RawNode::with_content_id(content_id);
+        optional_node.spawn_probability = SpawnProbability::Never.into();
+        optional_schematic.nodes.fill(optional_node);
+
+        schematic
+            .merge(&optional_schematic, (1, 1, 1).try_into().unwrap())
+            .unwrap();
+
+        assert_eq!(schematic.air_id(), Some(0));
+        let node = schematic.node_at((1, 1, 1).try_into().unwrap()).unwrap();
+        assert_eq!(node.content_name, "default:dry_dirt");
+    }
+
     #[fixture]
     fn schematic() -> Schematic {
         let mut schematic = Schematic::with_raw_nodes(
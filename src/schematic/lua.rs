@@ -0,0 +1,210 @@
+//! Converts a [Schematic] into the table format that Luanti's Lua API accepts wherever it expects
+//! a schematic, like `core.place_schematic()`.
+//!
+//! Lua API documentation: <https://github.com/luanti-org/luanti/blob/master/doc/lua_api.md#schematic-specifier>
+
+use std::fmt::Write;
+
+use crate::error::Error;
+use crate::node::{NodeSpace, RawNode, SpawnProbability};
+
+use super::Schematic;
+
+/// Name of the Lua variable that holds the shared entry for air nodes, when skipping air.
+const AIR_PLACEHOLDER: &str = "air";
+
+/// Renders the `schematic` as a Lua chunk that returns the schematic table. The result is a
+/// chunk rather than a table constructor, so it has to be run, e.g. with `loadstring()`, instead
+/// of being placed inside another expression.
+///
+/// The `data` table of the Lua API is positional, so air nodes can't simply be left out. When
+/// `skip_air` is set, plain air nodes instead all refer to one shared `{name = "air"}` table,
+/// which makes the output a lot smaller for sparse schematics.
+pub(super) fn to_lua_table(schematic: &Schematic, skip_air: bool) -> Result<String, Error> {
+    let mut output = String::new();
+
+    if skip_air {
+        writeln!(output, "local {AIR_PLACEHOLDER} = {{name = \"air\"}}\n").unwrap();
+    }
+
+    let dimensions = schematic.dimensions;
+    writeln!(output, "return {{").unwrap();
+    writeln!(
+        output,
+        "\tsize = {{x = {}, y = {}, z = {}}},",
+        dimensions.x, dimensions.y, dimensions.z
+    )
+    .unwrap();
+
+    writeln!(output, "\tyslice_prob = {{").unwrap();
+    for (y, probability) in schematic.layer_probabilities.iter().enumerate() {
+        writeln!(
+            output,
+            "\t\t{{ypos = {y}, prob = {}}},",
+            lua_probability(u8::from(probability))
+        )
+        .unwrap();
+    }
+    writeln!(output, "\t}},").unwrap();
+
+    writeln!(output, "\tdata = {{").unwrap();
+    for raw_node in &schematic.nodes {
+        let content_name = schematic
+            .content_name_for_id(raw_node.content_id)
+            .ok_or(Error::InvalidContentIndex(raw_node.content_id))?;
+
+        if skip_air && is_plain_air(schematic, raw_node) {
+            writeln!(output, "\t\t{AIR_PLACEHOLDER},").unwrap();
+        } else {
+            writeln!(output, "\t\t{},", lua_node(content_name, raw_node)).unwrap();
+        }
+    }
+    writeln!(output, "\t}},").unwrap();
+    writeln!(output, "}}").unwrap();
+
+    Ok(output)
+}
+
+/// Whether the node is air with only default values, i.e. whether it can be replaced by the
+/// shared air placeholder.
+fn is_plain_air(schematic: &Schematic, raw_node: &RawNode) -> bool {
+    Some(raw_node.content_id) == schematic.air_id
+        && raw_node.spawn_probability == SpawnProbability::Always.into()
+        && !raw_node.force_placement
+        && raw_node.param2 == 0
+}
+
+/// Renders a single entry of the `data` table. Fields with the same value as the Lua API's
+/// defaults are left out.
+fn lua_node(content_name: &str, raw_node: &RawNode) -> String {
+    let mut entry = format!("{{name = \"{}\"", escape_lua_string(content_name));
+
+    if raw_node.spawn_probability != SpawnProbability::Always.into() {
+        write!(
+            entry,
+            ", prob = {}",
+            lua_probability(raw_node.spawn_probability)
+        )
+        .unwrap();
+    }
+    if raw_node.param2 != 0 {
+        write!(entry, ", param2 = {}", raw_node.param2).unwrap();
+    }
+    if raw_node.force_placement {
+        entry.push_str(", force_place = true");
+    }
+    entry.push('}');
+
+    entry
+}
+
+/// MTS files store probabilities from 0 to 127, where the Lua API uses 0 to 255.
+fn lua_probability(probability: u8) -> u16 {
+    u16::from(probability.min(127)) * 2
+}
+
+/// Escapes `value` for use within a double quoted Lua string, including control characters like
+/// newlines, similar to `string.format("%q")`.
+fn escape_lua_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            // Always 3 digits, so a digit that follows isn't taken as part of the escape
+            c if c.is_ascii_control() => write!(escaped, "\\{:03}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::node::Node;
+
+    #[test]
+    fn test_to_lua_table() {
+        let mut schematic = Schematic::new((2, 1, 1).try_into().unwrap()).unwrap();
+        schematic
+            .place_node(
+                &Node::new(
+                    "default:torch".into(),
+                    SpawnProbability::Custom(50),
+                    true,
+                    3,
+                ),
+                (1, 0, 0).try_into().unwrap(),
+            )
+            .unwrap();
+
+        let lua = to_lua_table(&schematic, false).unwrap();
+
+        assert_eq!(
+            lua,
+            "return {
+\tsize = {x = 2, y = 1, z = 1},
+\tyslice_prob = {
+\t\t{ypos = 0, prob = 254},
+\t},
+\tdata = {
+\t\t{name = \"air\"},
+\t\t{name = \"default:torch\", prob = 100, param2 = 3, force_place = true},
+\t},
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_escape_lua_string() {
+        assert_eq!(escape_lua_string("default:stone"), "default:stone");
+        assert_eq!(
+            escape_lua_string("a\"b\\c\nd\re\tf"),
+            "a\\\"b\\\\c\\nd\\re\\tf"
+        );
+        assert_eq!(escape_lua_string("\x001"), "\\0001");
+        assert_eq!(escape_lua_string("\x1b\x7f"), "\\027\\127");
+        assert_eq!(escape_lua_string("möbel:stühl"), "möbel:stühl");
+    }
+
+    #[test]
+    fn test_to_lua_table_skip_air() {
+        let mut schematic = Schematic::new((8, 8, 8).try_into().unwrap()).unwrap();
+        schematic
+            .place_node(
+                &Node::with_content_name("default:stone".into()),
+                (4, 0, 4).try_into().unwrap(),
+            )
+            .unwrap();
+
+        let full = to_lua_table(&schematic, false).unwrap();
+        let sparse = to_lua_table(&schematic, true).unwrap();
+
+        assert!(sparse.starts_with("local air = {name = \"air\"}\n"));
+        assert!(
+            sparse.len() * 2 < full.len(),
+            "{} vs {}",
+            sparse.len(),
+            full.len()
+        );
+
+        // Every node still has its own entry, so positions line up with the full output
+        let data_entries = |lua: &str| lua.lines().filter(|line| line.starts_with("\t\t")).count();
+        assert_eq!(data_entries(&sparse), data_entries(&full));
+        assert_eq!(
+            sparse
+                .lines()
+                .position(|line| line.contains("default:stone")),
+            full.lines()
+                .position(|line| line.contains("default:stone"))
+                .map(|position| position + 2)
+        );
+    }
+}
@@ -1,17 +1,41 @@
+mod analysis;
+mod changes;
+mod content_name;
 mod editing;
+mod font;
+mod lua;
+#[cfg(feature = "obj")]
+mod obj;
 mod parser;
 mod serializer;
+mod shapes;
 
 use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::OnceLock;
 
-use ndarray::{Array3, ArrayView3, Axis, Dim};
+use flate2::Compression;
+use ndarray::{Array2, Array3, ArrayView3, ArrayViewMut3, Axis, Dim, s};
 
 use crate::error::Error;
 use crate::node::{AnnotatedNode, Node, NodeSpace, RawNode, SpawnProbability};
-use crate::vector::MapVector;
-
-#[derive(Debug, PartialEq, Eq, Clone)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+use crate::vector::{MAX_WORLD_COORDINATE, MIN_WORLD_COORDINATE, MapVector};
+
+use content_name::ContentName;
+
+pub use analysis::{Lint, SchematicSummary, SymmetryReport, common_content};
+pub use changes::{ChangeRecorder, NodeChange};
+pub(crate) use parser::parser_expected;
+
+/// Two `Schematic`s are equal when they would be stored the same way by
+/// [to_bytes()](Schematic::to_bytes), so their [name()](Schematic::name) doesn't matter.
+#[derive(Debug, Eq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "SchematicFields")
+)]
 pub struct Schematic {
     pub(crate) version: u16,
     pub dimensions: MapVector,
@@ -20,8 +44,23 @@ pub struct Schematic {
     /// the contents of a node, i.e. the type of block or items like torches.
     ///
     /// Examples of names are: "air", "default:cobble", "mcl_core:quartz"
-    pub(crate) content_names: Vec<String>,
+    pub(crate) content_names: Vec<ContentName>,
+    /// Content ID of "air" in `content_names`, if registered. Kept up to date by
+    /// `push_content_name()` and `set_content_names()`, so it doesn't need to be looked up all the
+    /// time.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) air_id: Option<u16>,
+    /// Content ID of "ignore" in `content_names`, if registered. See `air_id`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) ignore_id: Option<u16>,
     pub(crate) nodes: Array3<RawNode>,
+    /// A human-readable name for the `Schematic`. The MTS file format has no place for it, so it's
+    /// not stored by `to_bytes()`.
+    pub(crate) name: Option<String>,
+    /// Cached result of `digest()`. Every `&mut self` method that can change what's part of the
+    /// digest has to clear it.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) digest: OnceLock<u64>,
 }
 
 impl Schematic {
@@ -39,6 +78,34 @@ impl Schematic {
         Self::with_raw_nodes(dimensions, nodes)
     }
 
+    /// Creates a `Schematic` of "air" that already has the given `content_names` registered, in
+    /// that order, e.g. to use the same content IDs as another `Schematic` for fast merging. When
+    /// "air" isn't part of `content_names`, it's added as the first one.
+    ///
+    /// Returns a [DuplicateContentName](Error::DuplicateContentName) when a name is listed more
+    /// than once, or an [EmptyDimension](Error::EmptyDimension) when any of the `dimensions` is
+    /// zero.
+    pub fn new_with_content_table(
+        dimensions: MapVector,
+        mut content_names: Vec<String>,
+    ) -> Result<Self, Error> {
+        parser::verify_unique_name_ids(&content_names)?;
+        if !content_names.iter().any(|name| name == "air") {
+            content_names.insert(0, "air".to_string());
+        }
+
+        let mut schematic = Schematic::new(dimensions)?;
+        schematic.set_content_names(content_names.into_iter().map(ContentName::from).collect());
+        let air = schematic
+            .air_id
+            .expect("\"air\" should be part of the content names");
+        schematic.nodes.fill(RawNode::with_content_id(air));
+
+        Ok(schematic)
+    }
+
+    /// Returns an [EmptyDimension](Error::EmptyDimension) when any of the `dimensions` is zero.
+    ///
     /// Since [RawNode] does not contain the actual content names, using this constructor requires
     /// the caller to `register_content()` all the content names that the [RawNode]s content IDs
     /// point to and update those IDs, if necessary.
@@ -46,6 +113,10 @@ impl Schematic {
         dimensions: MapVector,
         nodes: T,
     ) -> Result<Self, Error> {
+        if dimensions.volume() == 0 {
+            return Err(Error::EmptyDimension);
+        }
+
         let nodes = nodes.into();
         let num_nodes = nodes.len();
         let nodes = Array3::from_shape_vec(dimensions.as_shape(), nodes).map_err(|_| {
@@ -58,6 +129,144 @@ impl Schematic {
         Ok(Self::with_array3(dimensions, nodes))
     }
 
+    /// Like [with_raw_nodes()](Schematic::with_raw_nodes), but takes the dimensions as a plain
+    /// `(x, y, z)` tuple.
+    pub fn with_raw_nodes_and_shape(
+        shape: (u16, u16, u16),
+        nodes: Vec<RawNode>,
+    ) -> Result<Schematic, Error> {
+        Self::with_raw_nodes(shape.try_into()?, nodes)
+    }
+
+    /// Builds a `Schematic` layer by layer, from the bottom up. Each layer is a square of `side` by
+    /// `side` nodes, ordered in the same way as in a MTS file: row by row along the Z-axis, with
+    /// the X-coordinate changing the fastest.
+    ///
+    /// Like with [with_raw_nodes()](Schematic::with_raw_nodes), the caller needs to
+    /// `register_content()` the content the [RawNode]s point to.
+    pub fn from_square_layers(side: u16, layers: Vec<Vec<RawNode>>) -> Result<Schematic, Error> {
+        let num_layers = u16::try_from(layers.len()).map_err(|_| Error::OutOfBounds)?;
+        let dimensions = MapVector::new(side, num_layers, side)?;
+        if dimensions.volume() == 0 {
+            return Err(Error::EmptyDimension);
+        }
+        let layer_size = side as usize * side as usize;
+
+        let mut nodes = Array3::default(dimensions.as_shape());
+        for (y, layer) in layers.into_iter().enumerate() {
+            if layer.len() != layer_size {
+                return Err(Error::IncorrectNodeCount {
+                    found: layer.len(),
+                    expected: layer_size,
+                });
+            }
+
+            Array2::from_shape_vec((side as usize, side as usize), layer)
+                .expect("layer to contain side * side nodes")
+                .assign_to(nodes.slice_mut(s![.., y, ..]));
+        }
+
+        Ok(Self::with_array3(dimensions, nodes))
+    }
+
+    /// Creates a `Schematic` with all of its six faces made of `wall`, and air inside, e.g. as
+    /// the shell of a room. When any of the `dimensions` is less than 3, there is no inside and
+    /// the whole `Schematic` consists of `wall`.
+    pub fn hollow_box(dimensions: MapVector, wall: Cow<'_, str>) -> Result<Schematic, Error> {
+        shapes::hollow_box(dimensions, wall)
+    }
+
+    /// Creates a `Schematic` of `2 * radii + 1` nodes along each axis, with an ellipsoid of
+    /// `material` in the center and air around it. With the same radius along every axis, the
+    /// ellipsoid is a sphere.
+    pub fn ellipsoid(radii: MapVector, material: Cow<'_, str>) -> Result<Schematic, Error> {
+        shapes::ellipsoid(radii, material)
+    }
+
+    /// Copies any [NodeSpace] into a new `Schematic`, with its dimensions, content names and
+    /// nodes. Properties that aren't part of `NodeSpace`, like the layer probabilities, get their
+    /// default values. For [SchematicRef]s, [to_owned_schematic()](SchematicRef::to_owned_schematic)
+    /// keeps those properties.
+    pub fn from_node_space<'space>(space: &'space impl NodeSpace<'space>) -> Schematic {
+        let nodes = space.nodes().as_standard_layout().into_owned();
+
+        let mut schematic = Schematic::with_array3(space.dimensions(), nodes);
+        schematic.set_content_names(space.content_names().map(ContentName::from).collect());
+
+        schematic
+    }
+
+    /// Creates a `Schematic` from the content names in `grid`, indexed as `grid[x][y][z]`, e.g. for
+    /// tests and scripts. Note that this is the opposite order of
+    /// [as_shape()](MapVector::as_shape), which is used for the node array. Every node gets the
+    /// default spawn probability, placement and `param2`.
+    ///
+    /// Returns an [IncorrectNodeCount](Error::IncorrectNodeCount) when not every row along an
+    /// axis has the same length, or an [EmptyDimension](Error::EmptyDimension) when the grid is
+    /// empty along any axis.
+    pub fn from_name_grid(grid: &[Vec<Vec<String>>]) -> Result<Schematic, Error> {
+        let size_x = grid.len();
+        let size_y = grid.first().map_or(0, Vec::len);
+        let size_z = grid
+            .first()
+            .and_then(|plane| plane.first())
+            .map_or(0, Vec::len);
+
+        for plane in grid {
+            if plane.len() != size_y {
+                return Err(Error::IncorrectNodeCount {
+                    found: plane.len(),
+                    expected: size_y,
+                });
+            }
+            if let Some(row) = plane.iter().find(|row| row.len() != size_z) {
+                return Err(Error::IncorrectNodeCount {
+                    found: row.len(),
+                    expected: size_z,
+                });
+            }
+        }
+
+        let to_dimension = |size: usize| u16::try_from(size).map_err(|_| Error::OutOfBounds);
+        let dimensions = MapVector::new(
+            to_dimension(size_x)?,
+            to_dimension(size_y)?,
+            to_dimension(size_z)?,
+        )?;
+
+        let mut schematic = Schematic::new(dimensions)?;
+        for (x, plane) in grid.iter().enumerate() {
+            for (y, row) in plane.iter().enumerate() {
+                for (z, name) in row.iter().enumerate() {
+                    let content_id = schematic.register_content(name.as_str().into());
+                    schematic.nodes[(z, y, x)] = RawNode::with_content_id(content_id);
+                }
+            }
+        }
+
+        Ok(schematic)
+    }
+
+    /// Creates a `Schematic` from an occupancy `grid`, as output by voxelizers, where occupied
+    /// cells become `material` and the others air. The `grid` is indexed like the node array,
+    /// see [as_shape()](MapVector::as_shape), and its shape becomes the dimensions.
+    ///
+    /// Returns an [OutOfBounds](Error::OutOfBounds) when the `grid` is too large for a
+    /// `Schematic`, or an [EmptyDimension](Error::EmptyDimension) when it's empty along any axis.
+    pub fn from_occupancy(grid: ArrayView3<bool>, material: Cow<str>) -> Result<Schematic, Error> {
+        let dimensions = MapVector::from_shape(grid.dim())?;
+        let mut schematic = Schematic::new(dimensions)?;
+
+        let material = RawNode::with_content_id(schematic.register_content(material));
+        schematic.nodes.zip_mut_with(&grid, |node, &occupied| {
+            if occupied {
+                *node = material;
+            }
+        });
+
+        Ok(schematic)
+    }
+
     fn with_array3(dimensions: MapVector, nodes: Array3<RawNode>) -> Self {
         Schematic {
             version: 4,
@@ -66,8 +275,12 @@ impl Schematic {
             // Let the caller provide a correct `MapVector` instead.
             dimensions,
             layer_probabilities: vec![SpawnProbability::Always; dimensions.y as usize],
-            content_names: vec!["air".to_string()],
+            content_names: vec!["air".into()],
+            air_id: Some(0),
+            ignore_id: None,
             nodes,
+            name: None,
+            digest: OnceLock::new(),
         }
     }
 
@@ -75,18 +288,152 @@ impl Schematic {
         parser::parse(input.as_ref())
     }
 
+    /// Like [from_bytes()](Schematic::from_bytes), but when parsing fails, returns a description
+    /// that includes all the details the parser has about what went wrong. Meant for debugging
+    /// malformed files.
+    pub fn from_bytes_verbose<T: AsRef<[u8]>>(input: T) -> Result<Schematic, String> {
+        Self::from_bytes(input).map_err(|error| match error {
+            Error::ParseError(parse_error) => parser::describe_error(&parse_error),
+            error => error.to_string(),
+        })
+    }
+
+    /// Like [from_bytes()](Schematic::from_bytes), but returns a [TooLarge](Error::TooLarge)
+    /// when the schematic has more than `max_nodes` nodes. This is checked before decompressing
+    /// or allocating anything for the nodes, so memory use stays proportional to `max_nodes`.
+    /// This is meant for loading untrusted files, e.g. uploaded by users of a server. For those
+    /// wrapped in gzip, see [from_gzip_limited()](Schematic::from_gzip_limited).
+    pub fn from_bytes_limited<T: AsRef<[u8]>>(
+        input: T,
+        max_nodes: usize,
+    ) -> Result<Schematic, Error> {
+        parser::parse_limited(input.as_ref(), max_nodes)
+    }
+
+    /// Like [from_bytes()](Schematic::from_bytes), but also accepts files of which the node data
+    /// is not zlib compressed, as written by some debugging tools. Whether the node data is
+    /// compressed is detected by checking for a zlib header.
+    pub fn from_bytes_allow_uncompressed<T: AsRef<[u8]>>(input: T) -> Result<Schematic, Error> {
+        parser::parse_allow_uncompressed(input.as_ref())
+    }
+
+    /// Like [from_bytes()](Schematic::from_bytes), but also returns a CRC32 checksum of the
+    /// decompressed node data, e.g. to detect that the nodes got corrupted somewhere between
+    /// loading and saving. As the checksum doesn't depend on how the data was compressed, the same
+    /// nodes give the same checksum.
+    pub fn from_bytes_with_checksum<T: AsRef<[u8]>>(input: T) -> Result<(Schematic, u32), Error> {
+        parser::parse_with_checksum(input.as_ref())
+    }
+
+    /// Like [from_bytes()](Schematic::from_bytes), but calls `progress` with a fraction from 0.0
+    /// to 1.0 after each major stage of parsing (the header, decompressing and the nodes), e.g.
+    /// for showing a progress bar while loading big files.
+    pub fn from_bytes_with_progress<T: AsRef<[u8]>, F: FnMut(f32)>(
+        input: T,
+        progress: F,
+    ) -> Result<Schematic, Error> {
+        parser::parse_with_progress(input.as_ref(), progress)
+    }
+
+    /// Like [from_bytes()](Schematic::from_bytes), but parses the `input` into this `Schematic`,
+    /// replacing all of its contents. When the dimensions of both schematics are the same, the
+    /// memory of the current nodes is reused, which saves allocations when loading a lot of
+    /// equally-sized schematics.
+    ///
+    /// When parsing fails the `Schematic` is left as it was.
+    pub fn load_into(&mut self, input: &[u8]) -> Result<(), Error> {
+        self.digest.take();
+        parser::parse_into(input, self)
+    }
+
+    /// Like [from_bytes()](Schematic::from_bytes), but for schematics that are additionally
+    /// compressed with gzip, like `.mts.gz` files.
+    ///
+    /// The whole gzip file is decompressed into memory before parsing, however large that turns
+    /// out to be, so use [from_gzip_limited()](Schematic::from_gzip_limited) for untrusted files.
+    pub fn from_gzip<T: AsRef<[u8]>>(input: T) -> Result<Schematic, Error> {
+        parser::parse_gzip(input.as_ref(), usize::MAX)
+    }
+
+    /// Like [from_gzip()](Schematic::from_gzip), but returns a
+    /// [DecompressionFailed](Error::DecompressionFailed) when the gzip file decompresses into
+    /// more than `max_size` bytes. Decompression stops right after that, so memory use stays
+    /// proportional to `max_size`.
+    pub fn from_gzip_limited<T: AsRef<[u8]>>(
+        input: T,
+        max_size: usize,
+    ) -> Result<Schematic, Error> {
+        parser::parse_gzip(input.as_ref(), max_size)
+    }
+
+    /// Like [from_bytes()](Schematic::from_bytes), but also [validate()](Schematic::validate)s the
+    /// parsed `Schematic` before returning it.
+    ///
+    /// The parser already refuses everything that `validate()` checks for: node data that's too
+    /// short or refers to unknown content is an error, and the MTS format stores exactly one layer
+    /// probability per Y-layer, so a mismatched number of them can't even be expressed. This
+    /// therefore never rejects input that `from_bytes()` accepts, and is only an explicit guard
+    /// for callers that want one.
+    pub fn from_bytes_validated<T: AsRef<[u8]>>(input: T) -> Result<Schematic, Error> {
+        let schematic = Self::from_bytes(input)?;
+        schematic.validate()?;
+
+        Ok(schematic)
+    }
+
+    /// Iterates over all nodes together with their coordinates, in the order they're stored: the
+    /// X-coordinate changes the fastest and the Z-coordinate the slowest, like
+    /// [AxisOrder::ZYX].
     pub fn annotated_nodes<'schematic>(&'schematic self) -> AnnotatedNodeIterator<'schematic> {
         AnnotatedNodeIterator::from_schematic(self)
     }
 
+    /// Like [annotated_nodes()](Schematic::annotated_nodes), but visits the nodes in the given
+    /// `order` of axes.
+    pub fn annotated_nodes_ordered(
+        &self,
+        order: AxisOrder,
+    ) -> impl Iterator<Item = AnnotatedNode<'_>> {
+        let axes = order.array_axes();
+        let shape = self.nodes.shape();
+
+        ndarray::indices((shape[axes[0]], shape[axes[1]], shape[axes[2]]))
+            .into_iter()
+            .map(move |(slowest, middle, fastest)| {
+                let mut index = [0; 3];
+                index[axes[0]] = slowest;
+                index[axes[1]] = middle;
+                index[axes[2]] = fastest;
+                let [z, y, x] = index;
+                let raw_node = &self.nodes[index];
+
+                let node = raw_node.to_node(self).expect(
+                    "Raw node's content ID should point to a content name in the schematic.",
+                );
+
+                AnnotatedNode {
+                    coordinates: MapVector {
+                        x: x as u16,
+                        y: y as u16,
+                        z: z as u16,
+                    },
+                    node,
+                }
+            })
+    }
+
     /// Registers a content name in the `Schematic`. Checks for duplicates.
     ///
+    /// New names are always appended to the content table, so the IDs of already registered
+    /// content never change, and the order of the table only depends on the order of registration.
+    ///
     /// Returns the content ID that `Node`s in this Schematic can point to.
     ///
     /// # Panics
     ///
     /// Panics when exceeding the limit of 65536 unique content names
     pub fn register_content(&mut self, name: Cow<'_, str>) -> u16 {
+        self.digest.take();
         // TODO Convert this field to a HashMap? But that would not be good for
         // `AnnotatedNodeIterator`
 
@@ -97,13 +444,112 @@ impl Schematic {
                     "A Schematic can only contain 65536 kinds of content"
                 );
 
-                self.content_names.push(name.into_owned());
-                (self.content_names.len() - 1) as u16
+                self.push_content_name(name.into_owned())
             }
             Some(content_id) => content_id,
         }
     }
 
+    /// Like [content_id_for_name()](NodeSpace::content_id_for_name), but ignores differences in
+    /// (ASCII) case, e.g. to find "Default:Stone" when cleaning up imported schematics that use
+    /// inconsistent casing. When several names match, returns the ID of the first one.
+    pub fn content_id_for_name_ci(&self, name: &str) -> Option<u16> {
+        self.content_names
+            .iter()
+            .position(|content_name| content_name.eq_ignore_ascii_case(name))
+            .map(|index| index as u16)
+    }
+
+    /// Registers all content `names` in the `Schematic`, like
+    /// [register_content()](Schematic::register_content) does.
+    ///
+    /// Returns the content IDs of the `names`, in the same order. Duplicate names get the same ID.
+    ///
+    /// # Panics
+    ///
+    /// Panics when exceeding the limit of 65536 unique content names
+    pub fn register_contents<I: IntoIterator<Item = String>>(&mut self, names: I) -> Vec<u16> {
+        self.digest.take();
+        names
+            .into_iter()
+            .map(|name| self.register_content(name.into()))
+            .collect()
+    }
+
+    /// Reserves capacity for at least `additional` more content names, to avoid repeated
+    /// reallocations when registering a lot of content at once.
+    pub fn reserve_content(&mut self, additional: usize) {
+        self.content_names.reserve(additional);
+    }
+
+    /// Returns the content names of all nodes, indexed as `grid[x][y][z]`. Note that this is the
+    /// opposite order of [as_shape()](MapVector::as_shape), which is used for the node array. See
+    /// [from_name_grid()](Schematic::from_name_grid) for the reverse.
+    pub fn to_name_grid(&self) -> Vec<Vec<Vec<String>>> {
+        let (size_z, size_y, size_x) = self.dimensions.as_shape();
+
+        let mut grid = vec![vec![vec![String::new(); size_z]; size_y]; size_x];
+        for ((z, y, x), raw_node) in self.nodes.indexed_iter() {
+            grid[x][y][z] = self
+                .content_name_for_id(raw_node.content_id)
+                .expect("Raw node's content ID should point to a content name in the schematic.")
+                .to_string();
+        }
+
+        grid
+    }
+
+    /// Returns the human-readable name of the `Schematic`, if it has one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Sets (or with `None`, removes) a human-readable name for the `Schematic`, e.g. for showing
+    /// in an editor.
+    ///
+    /// The name is carried over to the `Schematic`s created from this one, like by
+    /// [insert_layer()](Schematic::insert_layer), but it is not part of the MTS file format and
+    /// will be lost when saving with [to_bytes()](Schematic::to_bytes).
+    pub fn set_name(&mut self, name: Option<String>) {
+        self.name = name;
+    }
+
+    /// Returns the content ID of "air", if it is registered in this `Schematic`.
+    pub fn air_id(&self) -> Option<u16> {
+        self.air_id
+    }
+
+    /// Iterator for all registered content as `(content ID, content name)` pairs, in content ID
+    /// order.
+    pub fn content_entries(&self) -> impl Iterator<Item = (u16, &str)> {
+        self.content_names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| (index as u16, name.as_str()))
+    }
+
+    /// Adds `name` to the end of `content_names` without checking for duplicates, and returns its
+    /// content ID.
+    fn push_content_name(&mut self, name: String) -> u16 {
+        let content_id = self.content_names.len() as u16;
+        let name = ContentName::from(name);
+        match name.as_str() {
+            "air" if self.air_id.is_none() => self.air_id = Some(content_id),
+            "ignore" if self.ignore_id.is_none() => self.ignore_id = Some(content_id),
+            _ => {}
+        }
+        self.content_names.push(name);
+
+        content_id
+    }
+
+    /// Replaces all content names of this `Schematic`, without updating the nodes' content IDs.
+    fn set_content_names(&mut self, content_names: Vec<ContentName>) {
+        self.content_names = content_names;
+        self.air_id = self.content_id_for_name("air");
+        self.ignore_id = self.content_id_for_name("ignore");
+    }
+
     /// Checks if the `Schematic` has enough [Node]s to fill its entire space, that all
     /// [Node]s refer to a valid array index in `content_names`, and that there is a
     /// [SpawnProbability] for each Y-layer.
@@ -112,10 +558,10 @@ impl Schematic {
             return Err(Error::IncorrectNumberOfLayerProbabilities);
         }
 
-        if self.nodes.len() != self.dimensions.volume() {
+        if self.nodes.len() != self.volume() {
             return Err(Error::IncorrectNodeCount {
                 found: self.nodes.len(),
-                expected: self.dimensions.volume(),
+                expected: self.volume(),
             });
         }
 
@@ -128,10 +574,68 @@ impl Schematic {
         Ok(())
     }
 
+    /// The number of nodes that fit in the `Schematic`'s dimensions. For a valid `Schematic`,
+    /// this is the same as [num_nodes()](NodeSpace::num_nodes).
+    pub fn volume(&self) -> usize {
+        self.dimensions.volume()
+    }
+
+    /// Whether the `Schematic` lies completely within the limits of a Luanti world (from -31006 to
+    /// 31006 along each axis) when placed with its lowest corner at the world position `origin`.
+    pub fn fits_at_world_origin(&self, origin: (i32, i32, i32)) -> bool {
+        let fits_along_axis = |origin: i32, size: u16| {
+            let end = origin as i64 + size as i64 - 1;
+
+            origin >= MIN_WORLD_COORDINATE && end <= MAX_WORLD_COORDINATE as i64
+        };
+
+        fits_along_axis(origin.0, self.dimensions.x)
+            && fits_along_axis(origin.1, self.dimensions.y)
+            && fits_along_axis(origin.2, self.dimensions.z)
+    }
+
+    /// Whether the `Schematic` has no space for any nodes. The constructors already refuse to
+    /// create `Schematic`s with an empty dimension, but this keeps code that handles schematics
+    /// generically from having to rely on that.
+    pub fn is_empty(&self) -> bool {
+        self.volume() == 0
+    }
+
+    /// Iterator for the content ID of every node, in the order in which MTS files store them.
+    /// Together with [param1_plane()](Schematic::param1_plane) and
+    /// [param2_plane()](Schematic::param2_plane), this allows writing the node data in other ways
+    /// than [to_bytes()](Schematic::to_bytes) does.
+    pub fn content_id_plane(&self) -> impl Iterator<Item = u16> + '_ {
+        self.nodes.iter().map(|raw_node| raw_node.content_id)
+    }
+
+    /// Iterator for the param1 of every node, which combines the spawn probability and force
+    /// placement, in the order in which MTS files store them.
+    pub fn param1_plane(&self) -> impl Iterator<Item = u8> + '_ {
+        self.nodes.iter().map(|raw_node| raw_node.to_packed().1)
+    }
+
+    /// Iterator for the param2 of every node, in the order in which MTS files store them.
+    pub fn param2_plane(&self) -> impl Iterator<Item = u8> + '_ {
+        self.nodes.iter().map(|raw_node| raw_node.param2)
+    }
+
+    /// Whether every node of the `Schematic` is "air", e.g. to skip empty chunks from
+    /// [split_into_chunks()](Schematic::split_into_chunks).
+    pub fn is_all_air(&self) -> bool {
+        self.nodes
+            .iter()
+            .all(|raw_node| Some(raw_node.content_id) == self.air_id)
+    }
+
     /// Places the provided `Node` at `coordinates` in the schematic, overwriting whatever is there
     /// now.
     pub fn place_node(&mut self, node: &Node, coordinates: MapVector) -> Result<(), Error> {
-        if coordinates >= self.dimensions {
+        self.digest.take();
+        if coordinates.x >= self.dimensions.x
+            || coordinates.y >= self.dimensions.y
+            || coordinates.z >= self.dimensions.z
+        {
             return Err(Error::OutOfBounds);
         }
 
@@ -141,9 +645,142 @@ impl Schematic {
         Ok(())
     }
 
+    /// Returns the [RawNode] at the specified `coordinates`, without converting it to a [Node].
+    ///
+    /// Useful in hot loops that only need the content ID or param2 of nodes.
+    pub fn raw_node_at(&self, coordinates: MapVector) -> Option<&RawNode> {
+        self.nodes.get(coordinates.as_shape())
+    }
+
+    /// Whether the node at `coordinates` is "air". Returns `None` when the `coordinates` lie outside
+    /// of the `Schematic`.
+    pub fn is_air(&self, coordinates: MapVector) -> Option<bool> {
+        let raw_node = self.raw_node_at(coordinates)?;
+
+        Some(Some(raw_node.content_id) == self.air_id)
+    }
+
+    /// Returns the array that holds all [RawNode]s of this `Schematic`, for running custom ndarray
+    /// operations on them. The array is indexed in the order of
+    /// [MapVector::as_shape()](MapVector::as_shape), i.e. `(z, y, x)`.
+    pub fn raw_nodes(&self) -> &Array3<RawNode> {
+        &self.nodes
+    }
+
+    /// Mutable version of [raw_nodes()](Schematic::raw_nodes).
+    ///
+    /// It's up to the caller to keep the `Schematic` valid: the content IDs of the nodes must stay
+    /// within the registered content (see [register_content()](Schematic::register_content)), and
+    /// the shape of the array must not be changed. [validate()](Schematic::validate) can be used
+    /// to check this afterwards.
+    pub fn raw_nodes_mut(&mut self) -> &mut Array3<RawNode> {
+        self.digest.take();
+        &mut self.nodes
+    }
+
+    /// Returns a view of the box of `size` nodes starting at `from`, for running ndarray
+    /// operations on a part of the `Schematic` without copying it. Like with
+    /// [raw_nodes()](Schematic::raw_nodes), the view is indexed in `(z, y, x)` order.
+    ///
+    /// Returns an [OutOfBounds](Error::OutOfBounds) when the box doesn't fit in the `Schematic`.
+    pub fn region(
+        &self,
+        from: MapVector,
+        size: MapVector,
+    ) -> Result<ArrayView3<'_, RawNode>, Error> {
+        let to = self.region_end(from, size)?;
+
+        Ok(self.nodes.slice(s![
+            from.z as usize..to.z as usize,
+            from.y as usize..to.y as usize,
+            from.x as usize..to.x as usize
+        ]))
+    }
+
+    /// Mutable version of [region()](Schematic::region). The same warnings as for
+    /// [raw_nodes_mut()](Schematic::raw_nodes_mut) apply.
+    pub fn region_mut(
+        &mut self,
+        from: MapVector,
+        size: MapVector,
+    ) -> Result<ArrayViewMut3<'_, RawNode>, Error> {
+        self.digest.take();
+        let to = self.region_end(from, size)?;
+
+        Ok(self.nodes.slice_mut(s![
+            from.z as usize..to.z as usize,
+            from.y as usize..to.y as usize,
+            from.x as usize..to.x as usize
+        ]))
+    }
+
+    /// Returns the (exclusive) end coordinates of a box of `size` nodes starting at `from`, if
+    /// that box fits in the `Schematic`.
+    fn region_end(&self, from: MapVector, size: MapVector) -> Result<MapVector, Error> {
+        let to = from.checked_add(size).ok_or(Error::OutOfBounds)?;
+        if !to.fits_within(self.dimensions) {
+            return Err(Error::OutOfBounds);
+        }
+
+        Ok(to)
+    }
+
+    /// Like [place_node()](Schematic::place_node), but also returns whether the [Node]'s content
+    /// was newly registered in this `Schematic`, e.g. for keeping track of the materials a user
+    /// added.
+    pub fn place_node_tracked(
+        &mut self,
+        node: &Node,
+        coordinates: MapVector,
+    ) -> Result<bool, Error> {
+        self.digest.take();
+        let is_new_content = self.content_id_for_name(&node.content_name).is_none();
+        self.place_node(node, coordinates)?;
+
+        Ok(is_new_content)
+    }
+
+    /// Iterator for the nodes directly next to, above, and below the given `coordinates`, together
+    /// with their coordinates. Neighbors that would fall outside of the `Schematic` are skipped, so
+    /// there can be less than six.
+    pub fn neighbors(&self, coordinates: MapVector) -> impl Iterator<Item = (MapVector, Node<'_>)> {
+        self.neighbor_coordinates(coordinates)
+            .filter_map(|neighbor| Some((neighbor, self.node_at(neighbor)?)))
+    }
+
+    /// Iterator for the coordinates of the (up to six) orthogonal neighbors of `coordinates` that
+    /// lie within the `Schematic`.
+    fn neighbor_coordinates(&self, coordinates: MapVector) -> impl Iterator<Item = MapVector> {
+        const NEIGHBOR_OFFSETS: [(i16, i16, i16); 6] = [
+            (-1, 0, 0),
+            (1, 0, 0),
+            (0, -1, 0),
+            (0, 1, 0),
+            (0, 0, -1),
+            (0, 0, 1),
+        ];
+        let dimensions = self.dimensions;
+
+        NEIGHBOR_OFFSETS
+            .into_iter()
+            .filter_map(move |(offset_x, offset_y, offset_z)| {
+                let neighbor = MapVector {
+                    x: coordinates.x.checked_add_signed(offset_x)?,
+                    y: coordinates.y.checked_add_signed(offset_y)?,
+                    z: coordinates.z.checked_add_signed(offset_z)?,
+                };
+
+                (neighbor.x < dimensions.x
+                    && neighbor.y < dimensions.y
+                    && neighbor.z < dimensions.z)
+                    .then_some(neighbor)
+            })
+    }
+
     /// Converts a [Node] to a [RawNode], and registers the [Node]'s content in this `Schematic` if
     /// it isn't part of this schematic already.
     pub fn convert_node_to_raw_node(&mut self, node: &Node) -> RawNode {
+        self.digest.take();
         self.register_content(node.content_name.clone());
 
         node.to_raw_node(self)
@@ -203,6 +840,139 @@ impl Schematic {
         }
     }
 
+    /// Like [rotate_left()](Schematic::rotate_left), but consumes the `Schematic` and returns an
+    /// owned, rotated copy that can be edited further.
+    pub fn into_rotated_left(self) -> Schematic {
+        self.rotate_left().to_owned_schematic()
+    }
+
+    /// Like [rotate_right()](Schematic::rotate_right), but consumes the `Schematic` and returns an
+    /// owned, rotated copy that can be edited further.
+    pub fn into_rotated_right(self) -> Schematic {
+        self.rotate_right().to_owned_schematic()
+    }
+
+    /// Like [rotate_180()](Schematic::rotate_180), but consumes the `Schematic` and returns an
+    /// owned, rotated copy that can be edited further.
+    pub fn into_rotated_180(self) -> Schematic {
+        self.rotate_180().to_owned_schematic()
+    }
+
+    /// The [SpawnProbability] of each Y-layer, starting at the bottom layer.
+    pub fn layer_probabilities(&self) -> &[SpawnProbability] {
+        &self.layer_probabilities
+    }
+
+    /// Like [layer_probabilities()](Schematic::layer_probabilities), but allows changing all of
+    /// them at once. There is always exactly one probability per Y-layer, which is why this
+    /// returns a slice.
+    pub fn layer_probabilities_mut(&mut self) -> &mut [SpawnProbability] {
+        self.digest.take();
+        &mut self.layer_probabilities
+    }
+
+    /// Sets how likely it is that the Y-layer `y` is spawned at all.
+    ///
+    /// Returns an [OutOfBounds](Error::OutOfBounds) when there is no such layer.
+    pub fn set_layer_probability(
+        &mut self,
+        y: u16,
+        probability: SpawnProbability,
+    ) -> Result<(), Error> {
+        self.digest.take();
+        let layer_probability = self
+            .layer_probabilities
+            .get_mut(y as usize)
+            .ok_or(Error::OutOfBounds)?;
+        *layer_probability = probability;
+
+        Ok(())
+    }
+
+    /// Resets all nodes of the `Schematic` to "air", while keeping its dimensions and registered
+    /// content. "air" is registered if it wasn't yet.
+    pub fn clear(&mut self) {
+        self.digest.take();
+        let air = self.register_content("air".into());
+
+        self.nodes.fill(RawNode::with_content_id(air));
+    }
+
+    /// Makes sure the whole `Schematic` is always placed completely, by setting the spawn
+    /// probability of all nodes and layers to [SpawnProbability::Always], and force placing all
+    /// nodes.
+    pub fn normalize_placement(&mut self) {
+        self.digest.take();
+        for node in &mut self.nodes {
+            node.spawn_probability = SpawnProbability::Always.into();
+            node.force_placement = true;
+        }
+
+        self.layer_probabilities.fill(SpawnProbability::Always);
+    }
+
+    /// Draws a straight line of `node`s from `from` to `to` (inclusive), e.g. for beams or
+    /// wiring. Both ends have to lie within the `Schematic`, otherwise an
+    /// [OutOfBounds](Error::OutOfBounds) is returned.
+    pub fn draw_line(&mut self, from: MapVector, to: MapVector, node: &Node) -> Result<(), Error> {
+        self.digest.take();
+        shapes::draw_line(self, from, to, node)
+    }
+
+    /// Writes `text` with `material` nodes, using a built-in 5x7 font, e.g. for signs. The text
+    /// lies in the `plane` perpendicular to the given axis, with `at` as the bottom left corner of
+    /// the first character. It runs along X, or along Z for [Axis3::X], and upwards along Y, or
+    /// along Z for [Axis3::Y].
+    ///
+    /// Lowercase letters are drawn as uppercase ones, and characters that aren't in the font as a
+    /// question mark. Parts of the text that don't fit are left out, but `at` itself has to lie
+    /// within the `Schematic`, otherwise an [OutOfBounds](Error::OutOfBounds) is returned.
+    pub fn draw_text(
+        &mut self,
+        text: &str,
+        at: MapVector,
+        material: Cow<'_, str>,
+        plane: Axis3,
+    ) -> Result<(), Error> {
+        self.digest.take();
+        shapes::draw_text(self, text, at, material, plane)
+    }
+
+    /// Changes the size of the `Schematic` to `new_dimensions`, keeping the origin in place.
+    /// Nodes that don't fit anymore are dropped, and any added space is filled with
+    /// `fill_with_node`. Added Y-layers are always spawned.
+    ///
+    /// Returns an [EmptyDimension](Error::EmptyDimension) when any of the `new_dimensions` is
+    /// zero.
+    pub fn resize(
+        &mut self,
+        new_dimensions: MapVector,
+        fill_with_node: &Node,
+    ) -> Result<(), Error> {
+        self.digest.take();
+        editing::resize(self, new_dimensions, fill_with_node)
+    }
+
+    /// Starts recording the changes made to the nodes of this `Schematic`, e.g. for undo support
+    /// in an editor. Edits made through the returned [ChangeRecorder] are recorded until it's
+    /// dropped.
+    pub fn record_changes(&mut self) -> ChangeRecorder<'_> {
+        self.digest.take();
+        ChangeRecorder::new(self)
+    }
+
+    /// Writes each node of `changes` to its coordinates, in order. Applying the old nodes of
+    /// changes recorded by a [ChangeRecorder] in reverse order undoes them, and applying the new
+    /// nodes redoes them.
+    ///
+    /// Returns an [OutOfBounds](Error::OutOfBounds) when any of the coordinates lies outside of
+    /// the `Schematic`, or an [InvalidContentIndex](Error::InvalidContentIndex) when a node points
+    /// to unregistered content. Nothing is changed in that case.
+    pub fn apply_changes(&mut self, changes: &[(MapVector, RawNode)]) -> Result<(), Error> {
+        self.digest.take();
+        changes::apply_changes(self, changes)
+    }
+
     /// Starting at `from_position`, fills the given space with copies of the given `Node`
     /// (converted to a [RawNode])
     pub fn fill(
@@ -211,11 +981,47 @@ impl Schematic {
         fill_space: MapVector,
         node: &Node,
     ) -> Result<(), Error> {
+        self.digest.take();
         let raw_node = self.convert_node_to_raw_node(node);
 
         editing::fill(self, from_position, fill_space, raw_node)
     }
 
+    /// Starting at `from_position`, fills the given space with a random mix of the content in
+    /// `weights`, where each node gets a content with a chance proportional to its weight. All of
+    /// the content is registered, even if it happens not to be used.
+    ///
+    /// Returns an [InvalidWeights](Error::InvalidWeights) when `weights` is empty or contains a
+    /// weight that isn't a positive number.
+    #[cfg(feature = "rand")]
+    pub fn fill_weighted<R: rand::Rng>(
+        &mut self,
+        from_position: MapVector,
+        fill_space: MapVector,
+        weights: &[(Cow<str>, f64)],
+        rng: &mut R,
+    ) -> Result<(), Error> {
+        self.digest.take();
+        editing::fill_weighted(self, from_position, fill_space, weights, rng)
+    }
+
+    /// Like [fill()](Schematic::fill), but with a [RawNode] whose content ID already points to
+    /// content in this `Schematic`, so no content needs to be registered.
+    pub fn fill_raw(
+        &mut self,
+        from_position: MapVector,
+        fill_space: MapVector,
+        node: RawNode,
+    ) -> Result<(), Error> {
+        self.digest.take();
+        debug_assert!(
+            (node.content_id as usize) < self.content_names.len(),
+            "RawNode's content ID should point to content in this Schematic"
+        );
+
+        editing::fill(self, from_position, fill_space, node)
+    }
+
     /// Copies the current `Schematic` and adds a new layer with copies of `fill_with_node`
     /// (converted to a [RawNode]) inserted on given `y` axis.
     pub fn insert_layer(&self, y: u16, fill_with_node: &Node) -> Result<Schematic, Error> {
@@ -227,49 +1033,422 @@ impl Schematic {
     ///
     /// If the source `Schematic` doesn't fit in the target space, an
     /// [OutOfBounds](Error::OutOfBounds) will be returned.
+    ///
+    /// The source can also be a rotated [SchematicRef], e.g. from
+    /// [rotate_180()](Schematic::rotate_180), in which case the rotated nodes and dimensions are
+    /// used.
     pub fn merge<'schematic>(
         &mut self,
         source: &'schematic impl NodeSpace<'schematic>,
         merge_at: MapVector,
     ) -> Result<(), Error> {
+        self.digest.take();
         editing::merge(source, self, merge_at)
     }
 
-    /// Splits the `Schematic` up in smaller `Schematic`s, each of of `chunk_dimensions` in size.`
-    ///
-    /// The order of the chunks goes like this: first X, then Y, then Z.
-    ///
-    /// Because it only uses chunks of exact `chunk_dimensions` in size, any nodes that fall outside the
-    /// last chunk of that size won't be returned.
-    pub fn split_into_chunks(
-        &self,
-        chunk_dimensions: MapVector,
-    ) -> impl Iterator<Item = Schematic> {
-        // TODO Would be nice to be able to add coordinates to each item, either offsets within the
-        // original Schematic, or some position of the chunks relative to each other.
-        self.nodes
-            .exact_chunks(chunk_dimensions.as_shape())
-            .into_iter()
-            .map(move |chunk| {
-                let mut schematic = Schematic::with_array3(chunk_dimensions, chunk.to_owned());
-                // This is inaccurate, as not all content names of the original Schematic might be
-                // present in the smaller chunk, but the alternative would be to go through all
-                // nodes to gather the correct IDs, and adjust those IDs to their new position in
-                // the Schematic chunk's content_names array. That would be slow.
-                schematic.content_names.clone_from(&self.content_names);
-
-                schematic
-            })
+    /// Like [merge()](Schematic::merge), but every pasted node gets the given spawn `probability`
+    /// instead of its own, e.g. to scatter a decoration over terrain by passing
+    /// [SpawnProbability::Custom]. Nodes that the `source` marks as never to be placed keep
+    /// that probability.
+    pub fn merge_with_probability<'schematic>(
+        &mut self,
+        source: &'schematic impl NodeSpace<'schematic>,
+        merge_at: MapVector,
+        probability: SpawnProbability,
+    ) -> Result<(), Error> {
+        self.digest.take();
+        editing::merge_with_probability(source, self, merge_at, probability)
     }
 
-    pub fn to_bytes(&self) -> Vec<u8> {
-        serializer::to_bytes(self)
+    /// Like [merge()](Schematic::merge), but also returns which content ID each content ID of the
+    /// `source` got in this `Schematic`, e.g. to post-process the merged nodes.
+    pub fn merge_returning_map(
+        &mut self,
+        source: &Schematic,
+        merge_at: MapVector,
+    ) -> Result<HashMap<u16, u16>, Error> {
+        self.digest.take();
+        editing::merge_returning_map(source, self, merge_at)
     }
-}
 
-impl<'schematic> NodeSpace<'schematic> for Schematic {
-    fn content_names(&'schematic self) -> impl Iterator<Item = &'schematic str> {
-        self.content_names.iter().map(String::as_str)
+    /// Like [merge()](Schematic::merge), but the "air" nodes of the `source` leave this
+    /// `Schematic` untouched, instead of replacing its nodes with air. Useful for pasting a
+    /// decoration that has air around it onto an existing build.
+    pub fn merge_ignore_source_air(
+        &mut self,
+        source: &Schematic,
+        merge_at: MapVector,
+    ) -> Result<(), Error> {
+        self.digest.take();
+        editing::merge_ignore_source_air(source, self, merge_at)
+    }
+
+    /// Like [merge()](Schematic::merge), but when the `source` doesn't fit, this `Schematic` grows
+    /// along the X-, Y- and/or Z-axis until it does. The added space is filled with "air", and the
+    /// added layers are always spawned.
+    ///
+    /// Returns an [OutOfBounds](Error::OutOfBounds) when the grown `Schematic` would exceed the
+    /// maximum dimensions of a schematic.
+    pub fn stamp(&mut self, source: &Schematic, at: MapVector) -> Result<(), Error> {
+        self.digest.take();
+        editing::stamp(source, self, at)
+    }
+
+    /// Finds out which nodes of this `Schematic` would change when merging `source` into it at
+    /// `merge_at`, without actually merging, e.g. for highlighting them in an editor. Follows the
+    /// same rules as [merge()](Schematic::merge), so nodes that would be skipped, or replaced by an
+    /// identical node, are not included.
+    ///
+    /// Returns an [OutOfBounds](Error::OutOfBounds) when `merge()` would.
+    pub fn merge_preview(
+        &self,
+        source: &Schematic,
+        merge_at: MapVector,
+    ) -> Result<Vec<MapVector>, Error> {
+        editing::merge_preview(source, self, merge_at)
+    }
+
+    /// Creates a smaller copy of this `Schematic` by combining every block of `factor` nodes
+    /// into a single node, resulting in a `Schematic` of `dimensions / factor` in size.
+    ///
+    /// Each block becomes the most common non-air content in that block, with ties going to the
+    /// content with the lowest content ID. Blocks that consist only of air become air.
+    ///
+    /// Like with [split_into_chunks()](Schematic::split_into_chunks), any nodes that don't fit
+    /// into a whole block are dropped.
+    pub fn downscale(&self, factor: MapVector) -> Result<Schematic, Error> {
+        editing::downscale(self, factor)
+    }
+
+    /// Creates a bigger copy of this `Schematic` in which every node is replaced by a solid block
+    /// of `factor` copies of that node, resulting in a `Schematic` of `dimensions * factor` in
+    /// size.
+    ///
+    /// Returns an [OutOfBounds](Error::OutOfBounds) when the resulting dimensions would be
+    /// larger than a schematic can be.
+    pub fn upscale(&self, factor: MapVector) -> Result<Schematic, Error> {
+        editing::upscale(self, factor)
+    }
+
+    /// Copies a box of `size` nodes, starting at `source_from` in the `source` `Schematic`, into
+    /// this `Schematic` at `destination_from`, overwriting whatever is there. The content used by
+    /// the copied nodes is registered in this `Schematic` if needed.
+    ///
+    /// Unlike [merge()](Schematic::merge), every node in the box is copied as is, regardless of
+    /// its spawn probability.
+    ///
+    /// Returns an [OutOfBounds](Error::OutOfBounds) when the box doesn't fit in either of the
+    /// `Schematic`s.
+    pub fn copy_region(
+        &mut self,
+        source: &Schematic,
+        source_from: MapVector,
+        size: MapVector,
+        destination_from: MapVector,
+    ) -> Result<(), Error> {
+        self.digest.take();
+        editing::copy_region(source, source_from, size, self, destination_from)
+    }
+
+    /// Replaces every `target` node that has less than `min_same_neighbors` `target` nodes
+    /// directly next to, above, or below it with an `into` node. Which nodes get replaced is
+    /// decided before any of them are, so the result doesn't depend on the order of the nodes.
+    ///
+    /// Returns the number of nodes that were replaced, or an
+    /// [InvalidContentName](Error::InvalidContentName) when `target` isn't registered in this
+    /// `Schematic`.
+    pub fn erode(
+        &mut self,
+        target: &str,
+        into: &str,
+        min_same_neighbors: u8,
+    ) -> Result<usize, Error> {
+        self.digest.take();
+        editing::erode(self, target, into, min_same_neighbors)
+    }
+
+    /// Replaces every node for which `predicate` returns true with the `replacement` node,
+    /// registering its content name when needed. Unlike [erode()](Schematic::erode), this can look
+    /// at the whole node, e.g. to only replace stairs that face a certain way using its `param2`.
+    ///
+    /// Returns the number of nodes that were replaced, or an
+    /// [InvalidContentIndex](Error::InvalidContentIndex) when a node points to unregistered
+    /// content.
+    pub fn replace_matching<F: Fn(&Node) -> bool>(
+        &mut self,
+        predicate: F,
+        replacement: &Node,
+    ) -> Result<usize, Error> {
+        editing::replace_matching(self, predicate, replacement)
+    }
+
+    /// Moves every node by the signed `offset` along X, Y and Z, e.g. to align a build with
+    /// something else. Without `wrap`, nodes that are moved out of the `Schematic` are dropped,
+    /// and the positions they leave behind become "air". With `wrap`, the nodes that move out on
+    /// one side come back in on the opposite side.
+    ///
+    /// The layer probabilities are moved along with the nodes.
+    pub fn shift(&mut self, offset: (i32, i32, i32), wrap: bool) -> Result<(), Error> {
+        self.digest.take();
+        editing::shift(self, offset, wrap)
+    }
+
+    /// Like [merge()](Schematic::merge), but when the `source` doesn't fit in the target space,
+    /// the part of it that falls outside of this `Schematic` is left out instead of returning an
+    /// error.
+    ///
+    /// Returns an [OutOfBounds](Error::OutOfBounds) only when `merge_at` itself lies outside of
+    /// this `Schematic`.
+    pub fn merge_clipped(&mut self, source: &Schematic, merge_at: MapVector) -> Result<(), Error> {
+        self.digest.take();
+        editing::merge_clipped(source, self, merge_at)
+    }
+
+    /// Splits the `Schematic` up in smaller `Schematic`s, each of of `chunk_dimensions` in size.`
+    ///
+    /// The order of the chunks goes like this: first X, then Y, then Z.
+    ///
+    /// Because it only uses chunks of exact `chunk_dimensions` in size, any nodes that fall outside the
+    /// last chunk of that size won't be returned.
+    pub fn split_into_chunks(
+        &self,
+        chunk_dimensions: MapVector,
+    ) -> impl Iterator<Item = Schematic> {
+        // TODO Would be nice to be able to add coordinates to each item, either offsets within the
+        // original Schematic, or some position of the chunks relative to each other.
+        self.nodes
+            .exact_chunks(chunk_dimensions.as_shape())
+            .into_iter()
+            .map(move |chunk| self.chunk_to_schematic(chunk_dimensions, chunk))
+    }
+
+    /// Like [split_into_chunks()](Schematic::split_into_chunks), but leaves out the chunks that
+    /// only contain "air", e.g. when chunking a sparse world. Each chunk comes with its offset
+    /// within this `Schematic`.
+    pub fn split_into_chunks_nonempty(
+        &self,
+        chunk_dimensions: MapVector,
+    ) -> impl Iterator<Item = (MapVector, Schematic)> {
+        let (chunk_z, chunk_y, chunk_x) = chunk_dimensions.as_shape();
+
+        self.nodes
+            .exact_chunks(chunk_dimensions.as_shape())
+            .into_iter()
+            .enumerate()
+            .filter(|(_index, chunk)| {
+                chunk
+                    .iter()
+                    .any(|raw_node| Some(raw_node.content_id) != self.air_id)
+            })
+            .map(move |(index, chunk)| {
+                // The chunks are in the same order as the nodes: X changes the fastest, Z the
+                // slowest
+                let (_size_z, size_y, size_x) = self.dimensions.as_shape();
+                let chunks_x = size_x / chunk_x;
+                let chunks_y = size_y / chunk_y;
+                let offset = MapVector {
+                    x: (index % chunks_x * chunk_x) as u16,
+                    y: (index / chunks_x % chunks_y * chunk_y) as u16,
+                    z: (index / (chunks_x * chunks_y) * chunk_z) as u16,
+                };
+
+                (offset, self.chunk_to_schematic(chunk_dimensions, chunk))
+            })
+    }
+
+    /// Turns a `chunk` of this `Schematic`'s nodes into a `Schematic` of its own.
+    fn chunk_to_schematic(
+        &self,
+        chunk_dimensions: MapVector,
+        chunk: ArrayView3<'_, RawNode>,
+    ) -> Schematic {
+        let mut schematic = Schematic::with_array3(chunk_dimensions, chunk.to_owned());
+        // This is inaccurate, as not all content names of the original Schematic might be
+        // present in the smaller chunk, but the alternative would be to go through all
+        // nodes to gather the correct IDs, and adjust those IDs to their new position in
+        // the Schematic chunk's content_names array. That would be slow.
+        schematic.set_content_names(self.content_names.clone());
+        schematic.name.clone_from(&self.name);
+
+        schematic
+    }
+
+    /// Returns an overview of the `Schematic`'s most important properties, e.g. for showing in
+    /// command line tools. [SchematicSummary] implements `Display` for this purpose.
+    pub fn summary(&self) -> SchematicSummary {
+        analysis::summary(self)
+    }
+
+    /// Checks whether the `Schematic` looks the same after mirroring it along each of the axes,
+    /// or after rotating it by 180 degrees. Nodes are compared by their content name only, so
+    /// e.g. differently rotated stairs are considered the same.
+    pub fn symmetry(&self) -> SymmetryReport {
+        analysis::symmetry(self)
+    }
+
+    /// Walks through the `Schematic` looking for common authoring mistakes, such as nodes that are
+    /// both force placed and have a spawn probability, or content that isn't used by any node.
+    pub fn lint(&self) -> Vec<Lint> {
+        analysis::lint(self)
+    }
+
+    /// Groups all `name` nodes into components of nodes that are connected directly next to,
+    /// above or below each other, e.g. to count separate ore deposits or rooms. The components are
+    /// ordered by their first node in the order in which nodes are stored.
+    ///
+    /// Returns an [InvalidContentName](Error::InvalidContentName) when `name` isn't registered in
+    /// this `Schematic`.
+    pub fn connected_components(&self, name: &str) -> Result<Vec<Vec<MapVector>>, Error> {
+        analysis::connected_components(self, name)
+    }
+
+    /// Computes a digest of the dimensions, layer probabilities, content names and nodes. Equal
+    /// schematics always have the same digest, but the digest isn't stable across Rust versions,
+    /// so don't store it.
+    ///
+    /// The digest is cached until the `Schematic` is changed, so to find duplicates in a large
+    /// library of schematics, they can be grouped by their digest cheaply. Only the schematics
+    /// within a group still have to be compared, e.g. with
+    /// [likely_equal()](Schematic::likely_equal).
+    ///
+    /// The name and version aren't part of the digest.
+    pub fn digest(&self) -> u64 {
+        *self.digest.get_or_init(|| {
+            let mut hasher = DefaultHasher::new();
+            self.nodes.dim().hash(&mut hasher);
+            self.layer_probabilities.hash(&mut hasher);
+            self.content_names.hash(&mut hasher);
+            self.nodes.iter().for_each(|node| node.hash(&mut hasher));
+
+            hasher.finish()
+        })
+    }
+
+    /// Whether this `Schematic` equals `other`. Different [digest()](Schematic::digest)s rule out
+    /// equality right away, so comparing the same schematics over and over mostly only compares
+    /// their cached digests. Otherwise they're compared with `==`.
+    pub fn likely_equal(&self, other: &Schematic) -> bool {
+        self.digest() == other.digest() && self == other
+    }
+
+    /// Counts the nodes for which `predicate` returns true, given their coordinates and the node
+    /// itself, e.g. to count the optional nodes above a certain height.
+    pub fn count_where<F: Fn(MapVector, &Node) -> bool>(&self, predicate: F) -> usize {
+        self.annotated_nodes()
+            .filter(|annotated_node| predicate(annotated_node.coordinates, &annotated_node.node))
+            .count()
+    }
+
+    /// Counts how the nodes will be placed, as `(always_forced, optional, never)`. `always_forced`
+    /// nodes always spawn and replace whatever is in the world, `never` nodes are never placed,
+    /// and `optional` nodes are all others: those with a custom spawn probability, or that don't
+    /// replace non-air nodes. Layer probabilities aren't taken into account.
+    pub fn placement_stats(&self) -> (usize, usize, usize) {
+        analysis::placement_stats(self)
+    }
+
+    /// Counts how many nodes of each content there are in each Y-layer, starting at the bottom
+    /// layer.
+    pub fn layer_compositions(&self) -> Vec<HashMap<String, usize>> {
+        analysis::layer_compositions(self)
+    }
+
+    /// Finds the top surface of every column of nodes, e.g. for placing structures on a terrain
+    /// schematic. The returned array is indexed by `(x, z)`, and holds the highest Y coordinate of
+    /// a node that is not air in that column, or `None` if the column only consists of air.
+    pub fn surface_heightmap(&self) -> Array2<Option<u16>> {
+        analysis::surface_heightmap(self)
+    }
+
+    /// Finds runs of consecutive Y-layers that are identical, i.e. have the same content and
+    /// param2 at every position. Returns the Y-coordinate of the first layer of each run, and the
+    /// number of layers in it. Layers that aren't the same as their neighbors are left out.
+    pub fn identical_layer_runs(&self) -> Vec<(u16, u16)> {
+        analysis::identical_layer_runs(self)
+    }
+
+    /// Finds the smallest box that contains all nodes of the content `name`, e.g. to find out
+    /// where all the water is. Returns the minimum and maximum corner of the box (both
+    /// inclusive), or `None` when no node has that content.
+    ///
+    /// Returns an [InvalidContentName](Error::InvalidContentName) when `name` isn't registered.
+    pub fn bounding_box_of(&self, name: &str) -> Result<Option<(MapVector, MapVector)>, Error> {
+        analysis::bounding_box_of(self, name)
+    }
+
+    /// Compares the layout of two `Schematic`s: whether they have the same dimensions, and the
+    /// same content and param2 at every position. Spawn probabilities and force placement are
+    /// ignored, as are differences in how content is registered.
+    pub fn equals_layout(&self, other: &Schematic) -> bool {
+        analysis::equals_layout(self, other)
+    }
+
+    /// Iterator for the distinct content names that are used by at least one node, in content ID
+    /// order.
+    ///
+    /// Unlike [NodeSpace::content_names()], this skips content that is registered in the
+    /// `Schematic` but not used by any of its nodes.
+    pub fn used_content_names(&self) -> impl Iterator<Item = &str> {
+        analysis::used_content_names(self)
+    }
+
+    /// Checks that the nodes of this `Schematic` only use content from the `allowed` set. "air" is
+    /// always allowed, and content that is registered but not used by any node is ignored.
+    ///
+    /// Returns the names of the used content that isn't part of `allowed` as the error.
+    pub fn uses_only(&self, allowed: &HashSet<&str>) -> Result<(), Vec<String>> {
+        analysis::uses_only(self, allowed)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        serializer::to_bytes(self, Compression::default())
+    }
+
+    /// Like [to_bytes()](Schematic::to_bytes), but stores the node data without actually
+    /// compressing it. The output is bigger, but always the same for the same `Schematic`, which
+    /// makes it suitable for comparing against "golden" files in tests. Luanti can load these
+    /// files just fine.
+    pub fn to_bytes_uncompressed(&self) -> Vec<u8> {
+        serializer::to_bytes(self, Compression::none())
+    }
+
+    /// Converts the `Schematic` into a Lua chunk that returns a schematic table, as accepted by
+    /// the Lua API of Luanti, e.g. `core.place_schematic()`.
+    ///
+    /// Note that the result is a chunk starting with `return`, and with `skip_air` also a
+    /// `local` declaration before that, not a table constructor. Run it to get the table, e.g.
+    /// with `loadstring(lua)()`, instead of embedding it in other Lua code.
+    ///
+    /// With `skip_air`, air nodes are written as a reference to a single shared air entry instead
+    /// of a table of their own, which makes the output much smaller for sparse schematics.
+    ///
+    /// Returns an [InvalidContentIndex](Error::InvalidContentIndex) when a node refers to content
+    /// that isn't registered.
+    pub fn to_lua_table(&self, skip_air: bool) -> Result<String, Error> {
+        lua::to_lua_table(self, skip_air)
+    }
+
+    /// Converts the `Schematic` into a Wavefront OBJ mesh, e.g. to share a build as a 3D preview.
+    /// Every node except air and "ignore" becomes a cube, colored by looking up its content name
+    /// in `palette`. Content that isn't in the `palette` is gray.
+    ///
+    /// With `cull_interior_faces`, faces between two neighbouring cubes are left out, which makes
+    /// the mesh a lot smaller for solid builds.
+    #[cfg(feature = "obj")]
+    pub fn to_obj(&self, palette: &HashMap<String, [f32; 3]>, cull_interior_faces: bool) -> String {
+        obj::to_obj(self, palette, cull_interior_faces)
+    }
+
+    /// Like [to_bytes()](Schematic::to_bytes), but additionally compresses the result with gzip,
+    /// which can be read back using [from_gzip()](Schematic::from_gzip).
+    pub fn to_gzip(&self) -> Vec<u8> {
+        serializer::to_gzip(self)
+    }
+}
+
+impl<'schematic> NodeSpace<'schematic> for Schematic {
+    fn content_names(&'schematic self) -> impl Iterator<Item = &'schematic str> {
+        self.content_names.iter().map(ContentName::as_str)
     }
 
     fn content_id_for_name(&'schematic self, name: &str) -> Option<u16> {
@@ -283,7 +1462,7 @@ impl<'schematic> NodeSpace<'schematic> for Schematic {
     }
 
     fn content_name_for_id(&'schematic self, id: u16) -> Option<&'schematic str> {
-        self.content_names.get(id as usize).map(String::as_str)
+        self.content_names.get(id as usize).map(ContentName::as_str)
     }
 
     fn dimensions(&'schematic self) -> MapVector {
@@ -305,6 +1484,59 @@ impl<'schematic> NodeSpace<'schematic> for Schematic {
     }
 }
 
+impl PartialEq for Schematic {
+    fn eq(&self, other: &Self) -> bool {
+        // The air and ignore IDs follow from the content names
+        self.version == other.version
+            && self.dimensions == other.dimensions
+            && self.layer_probabilities == other.layer_probabilities
+            && self.content_names == other.content_names
+            && self.nodes == other.nodes
+    }
+}
+
+/// The fields of a [Schematic] that are deserialized, after which the others are derived from
+/// them.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SchematicFields {
+    version: u16,
+    dimensions: MapVector,
+    layer_probabilities: Vec<SpawnProbability>,
+    content_names: Vec<ContentName>,
+    nodes: Array3<RawNode>,
+    name: Option<String>,
+}
+
+#[cfg(feature = "serde")]
+impl From<SchematicFields> for Schematic {
+    fn from(fields: SchematicFields) -> Self {
+        let mut schematic = Schematic::with_array3(fields.dimensions, fields.nodes);
+        schematic.version = fields.version;
+        schematic.layer_probabilities = fields.layer_probabilities;
+        schematic.set_content_names(fields.content_names);
+        schematic.name = fields.name;
+
+        schematic
+    }
+}
+
+impl TryFrom<&[u8]> for Schematic {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Schematic::from_bytes(value)
+    }
+}
+
+impl TryFrom<Vec<u8>> for Schematic {
+    type Error = Error;
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        Schematic::from_bytes(value)
+    }
+}
+
 /// Contains a modified view of a `Schematic`'s nodes, e.g. they have been rotated, or cut up
 /// somehow.
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -320,6 +1552,35 @@ impl<'schematic> SchematicRef<'schematic> {
             nodes_view: schematic.nodes.view(),
         }
     }
+
+    /// Copies the nodes of this view into a new `Schematic`, with the dimensions of the view. All
+    /// other properties, like the content names, are taken from the referenced `Schematic`.
+    pub fn to_owned_schematic(&self) -> Schematic {
+        // Views can have their axes swapped or inverted, so make sure the copy is stored in the
+        // usual order
+        let nodes = self.nodes_view.as_standard_layout().into_owned();
+        let dimensions = MapVector::from_shape(nodes.dim())
+            .expect("a view to have at most the dimensions of its Schematic");
+
+        let mut schematic = Schematic::with_array3(dimensions, nodes);
+        schematic.version = self.schematic.version;
+        schematic
+            .layer_probabilities
+            .clone_from(&self.schematic.layer_probabilities);
+        schematic.set_content_names(self.schematic.content_names.clone());
+        schematic.name.clone_from(&self.schematic.name);
+
+        schematic
+    }
+
+    /// The [SpawnProbability] of each Y-layer of the referenced `Schematic`.
+    ///
+    /// All of the rotations `SchematicRef` is created by keep the Y-axis in place, so these are
+    /// the same as the original `Schematic`'s. Rotating along the X- or Z-axis would move nodes to
+    /// other layers, which would make these probabilities invalid.
+    pub fn layer_probabilities(&self) -> &[SpawnProbability] {
+        &self.schematic.layer_probabilities
+    }
 }
 
 impl<'schematic> NodeSpace<'schematic> for SchematicRef<'schematic> {
@@ -335,8 +1596,17 @@ impl<'schematic> NodeSpace<'schematic> for SchematicRef<'schematic> {
         self.schematic.content_name_for_id(id)
     }
 
+    /// The dimensions of the view, which can differ from those of the referenced `Schematic`,
+    /// e.g. when it's rotated or clipped. This is what merging a `SchematicRef` needs to check
+    /// whether it fits.
     fn dimensions(&'schematic self) -> MapVector {
-        self.schematic.dimensions
+        let (z, y, x) = self.nodes_view.dim();
+
+        MapVector {
+            x: x as u16,
+            y: y as u16,
+            z: z as u16,
+        }
     }
 
     fn num_nodes(&'schematic self) -> usize {
@@ -354,8 +1624,51 @@ impl<'schematic> NodeSpace<'schematic> for SchematicRef<'schematic> {
     }
 }
 
+/// One of the three axes of a [Schematic].
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum Axis3 {
+    X,
+    Y,
+    Z,
+}
+
+/// The order in which to visit the axes when iterating over nodes, from the axis whose coordinate
+/// changes the slowest to the one that changes the fastest.
+///
+/// Nodes are stored (in memory and in MTS files) in [AxisOrder::ZYX] order.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum AxisOrder {
+    XYZ,
+    XZY,
+    YXZ,
+    YZX,
+    ZXY,
+    ZYX,
+}
+
+impl AxisOrder {
+    /// The axes of the node array (which are in `(z, y, x)` order), from slowest to fastest
+    fn array_axes(self) -> [usize; 3] {
+        const Z: usize = 0;
+        const Y: usize = 1;
+        const X: usize = 2;
+
+        match self {
+            AxisOrder::XYZ => [X, Y, Z],
+            AxisOrder::XZY => [X, Z, Y],
+            AxisOrder::YXZ => [Y, X, Z],
+            AxisOrder::YZX => [Y, Z, X],
+            AxisOrder::ZXY => [Z, X, Y],
+            AxisOrder::ZYX => [Z, Y, X],
+        }
+    }
+}
+
 /// Iterator for a collection of `Node` with some added metadata as how the `Node` relates to the
 /// `Schematic` its in.
+///
+/// The iterator is `Send` and knows its exact length, so it can be handed to rayon's
+/// `par_bridge()` to process the nodes in parallel.
 pub struct AnnotatedNodeIterator<'schematic> {
     current_x: u16,
     current_y: u16,
@@ -394,19 +1707,69 @@ impl<'schematic> Iterator for AnnotatedNodeIterator<'schematic> {
             None => return None,
         };
 
-        self.current_z += 1;
-        if self.current_z == self.schematic.dimensions.z {
-            self.current_z = 0;
+        // Nodes are stored with the X-coordinate changing the fastest and the Z-coordinate the
+        // slowest
+        self.current_x += 1;
+        if self.current_x == self.schematic.dimensions.x {
+            self.current_x = 0;
             self.current_y += 1;
         }
 
         if self.current_y == self.schematic.dimensions.y {
             self.current_y = 0;
-            self.current_x += 1;
+            self.current_z += 1;
         }
 
         Some(item)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.nodes_iter.size_hint()
+    }
+}
+
+impl ExactSizeIterator for AnnotatedNodeIterator<'_> {}
+
+impl IntoIterator for Schematic {
+    type Item = (MapVector, RawNode);
+    type IntoIter = OwnedNodeIterator;
+
+    /// Consumes the `Schematic`, returning each of its [RawNode]s together with its coordinates.
+    fn into_iter(self) -> Self::IntoIter {
+        OwnedNodeIterator {
+            dimensions: self.dimensions,
+            index: 0,
+            nodes_iter: self.nodes.into_iter(),
+        }
+    }
+}
+
+/// Iterator for the [RawNode]s of a consumed `Schematic`, together with their coordinates.
+pub struct OwnedNodeIterator {
+    dimensions: MapVector,
+    index: usize,
+    nodes_iter: ndarray::iter::IntoIter<RawNode, Dim<[usize; 3]>>,
+}
+
+impl Iterator for OwnedNodeIterator {
+    type Item = (MapVector, RawNode);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let raw_node = self.nodes_iter.next()?;
+
+        // Nodes are stored in the same order as in a MTS file, with the X-coordinate changing the
+        // fastest and the Z-coordinate the slowest
+        let width = self.dimensions.x as usize;
+        let layer_size = width * self.dimensions.y as usize;
+        let coordinates = MapVector {
+            x: (self.index % width) as u16,
+            y: ((self.index % layer_size) / width) as u16,
+            z: (self.index / layer_size) as u16,
+        };
+        self.index += 1;
+
+        Some((coordinates, raw_node))
+    }
 }
 
 #[cfg(test)]
@@ -428,7 +1791,7 @@ mod tests {
         );
 
         let annotated_node = nodes_iter.next().unwrap();
-        assert_eq!(annotated_node.coordinates, (0, 0, 1).try_into().unwrap());
+        assert_eq!(annotated_node.coordinates, (1, 0, 0).try_into().unwrap());
         assert_eq!(
             annotated_node.node,
             schematic.nodes[(0, 0, 1)].to_node(&schematic).unwrap()
@@ -444,7 +1807,7 @@ mod tests {
 
         let mut nodes_iter = nodes_iter.skip(2);
         let annotated_node = nodes_iter.next().unwrap();
-        assert_eq!(annotated_node.coordinates, (1, 0, 0).try_into().unwrap());
+        assert_eq!(annotated_node.coordinates, (0, 0, 1).try_into().unwrap());
         assert_eq!(
             annotated_node.node,
             schematic.nodes[(1, 0, 0)].to_node(&schematic).unwrap()
@@ -460,6 +1823,112 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_annotated_nodes_coordinates() {
+        let mut schematic = Schematic::new((3, 2, 4).try_into().unwrap()).unwrap();
+        for (index, node) in schematic.raw_nodes_mut().iter_mut().enumerate() {
+            node.param2 = index as u8;
+        }
+
+        for (index, annotated_node) in schematic.annotated_nodes().enumerate() {
+            let coordinates = annotated_node.coordinates;
+            assert_eq!(annotated_node.node.param2, index as u8);
+            assert_eq!(schematic.node_at(coordinates), Some(annotated_node.node));
+            assert_eq!(
+                index,
+                coordinates.x as usize + 3 * (coordinates.y as usize + 2 * coordinates.z as usize)
+            );
+        }
+    }
+
+    #[rstest]
+    fn test_annotated_nodes_len(schematic: Schematic) {
+        let iter = schematic.annotated_nodes();
+
+        assert_eq!(iter.len(), schematic.num_nodes());
+        assert_eq!(iter.size_hint(), (18, Some(18)));
+    }
+
+    #[rstest]
+    fn test_annotated_nodes_in_parallel(schematic: Schematic) {
+        use rayon::iter::{ParallelBridge, ParallelIterator};
+
+        let mut iter = schematic.annotated_nodes();
+        assert_eq!(iter.len(), 18);
+        iter.next();
+        assert_eq!(iter.len(), 17);
+
+        let serial_sum: u64 = schematic
+            .annotated_nodes()
+            .map(|node| u64::from(node.node.to_raw_node(&schematic).unwrap().content_id))
+            .sum();
+        let parallel_sum: u64 = schematic
+            .annotated_nodes()
+            .par_bridge()
+            .map(|node| u64::from(node.node.to_raw_node(&schematic).unwrap().content_id))
+            .sum();
+
+        assert_eq!(parallel_sum, serial_sum);
+        assert_eq!(serial_sum, (1..=18).sum());
+    }
+
+    #[rstest]
+    fn test_annotated_nodes_ordered(schematic: Schematic) {
+        let coordinates = |order| {
+            schematic
+                .annotated_nodes_ordered(order)
+                .take(4)
+                .map(|annotated_node| {
+                    let MapVector { x, y, z } = annotated_node.coordinates;
+                    (x, y, z)
+                })
+                .collect::<Vec<(u16, u16, u16)>>()
+        };
+
+        assert_eq!(
+            coordinates(AxisOrder::ZYX),
+            &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (0, 1, 0)]
+        );
+        assert_eq!(
+            coordinates(AxisOrder::XYZ),
+            &[(0, 0, 0), (0, 0, 1), (0, 0, 2), (0, 1, 0)]
+        );
+        assert_eq!(
+            coordinates(AxisOrder::YZX),
+            &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (0, 0, 1)]
+        );
+
+        for order in [AxisOrder::XZY, AxisOrder::YXZ, AxisOrder::ZXY] {
+            assert_eq!(
+                schematic.annotated_nodes_ordered(order).count(),
+                schematic.num_nodes()
+            );
+        }
+        assert!(
+            schematic
+                .annotated_nodes_ordered(AxisOrder::XYZ)
+                .all(|annotated_node| Some(annotated_node.node)
+                    == schematic.node_at(annotated_node.coordinates))
+        );
+        assert!(
+            schematic
+                .annotated_nodes_ordered(AxisOrder::ZYX)
+                .eq(schematic.annotated_nodes())
+        );
+    }
+
+    #[rstest]
+    fn test_into_iter(schematic: Schematic) {
+        let volume = schematic.dimensions.volume();
+        let expected_node = schematic.nodes[(1, 0, 2)];
+
+        let nodes = schematic.into_iter().collect::<Vec<(MapVector, RawNode)>>();
+
+        assert_eq!(nodes.len(), volume);
+        assert_eq!(nodes[0].0, (0, 0, 0).try_into().unwrap());
+        assert_eq!(nodes[8], ((2, 0, 1).try_into().unwrap(), expected_node));
+    }
+
     #[rstest]
     fn test_node_at(schematic: Schematic) {
         assert_eq!(
@@ -475,6 +1944,153 @@ mod tests {
         assert_eq!(schematic.node_at((999, 999, 999).try_into().unwrap()), None);
     }
 
+    #[test]
+    fn test_from_bytes_validated() {
+        let data = DATA_3X3;
+
+        let schematic = Schematic::from_bytes_validated(data).unwrap();
+        assert_eq!(schematic, Schematic::from_bytes(data).unwrap());
+    }
+
+    #[test]
+    fn test_new_with_empty_dimension() {
+        assert!(matches!(
+            Schematic::new((0, 1, 1).try_into().unwrap()),
+            Err(Error::EmptyDimension)
+        ));
+        assert!(matches!(
+            Schematic::with_raw_nodes((1, 1, 0).try_into().unwrap(), vec![]),
+            Err(Error::EmptyDimension)
+        ));
+        assert!(matches!(
+            Schematic::from_square_layers(2, vec![]),
+            Err(Error::EmptyDimension)
+        ));
+
+        Schematic::new((1, 1, 1).try_into().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_with_raw_nodes_and_shape() {
+        let schematic =
+            Schematic::with_raw_nodes_and_shape((2, 1, 3), vec![RawNode::default(); 6]).unwrap();
+        assert_eq!(schematic.dimensions, (2, 1, 3).try_into().unwrap());
+
+        Schematic::with_raw_nodes_and_shape((2, 2, 3), vec![RawNode::default(); 6]).unwrap_err();
+    }
+
+    #[test]
+    fn test_from_square_layers() {
+        let mut schematic = Schematic::from_square_layers(
+            2,
+            vec![
+                vec![RawNode::with_content_id(1); 4],
+                vec![
+                    RawNode::with_content_id(0),
+                    RawNode::with_content_id(2),
+                    RawNode::with_content_id(0),
+                    RawNode::with_content_id(0),
+                ],
+            ],
+        )
+        .unwrap();
+        schematic.register_content("default:dirt".into());
+        schematic.register_content("default:torch".into());
+
+        schematic.validate().unwrap();
+        assert_eq!(schematic.dimensions, (2, 2, 2).try_into().unwrap());
+        assert_eq!(
+            schematic
+                .node_at((0, 0, 1).try_into().unwrap())
+                .unwrap()
+                .content_name,
+            "default:dirt"
+        );
+        assert_eq!(
+            schematic
+                .node_at((1, 1, 0).try_into().unwrap())
+                .unwrap()
+                .content_name,
+            "default:torch"
+        );
+    }
+
+    #[test]
+    fn test_from_square_layers_with_incorrect_layer() {
+        let result = Schematic::from_square_layers(
+            2,
+            vec![
+                vec![RawNode::with_content_id(0); 4],
+                vec![RawNode::with_content_id(0); 3],
+            ],
+        );
+
+        assert!(matches!(
+            result,
+            Err(Error::IncorrectNodeCount {
+                found: 3,
+                expected: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn test_try_from_bytes() {
+        let data = DATA_3X3.to_vec();
+
+        let schematic: Schematic = data.as_slice().try_into().unwrap();
+        assert_eq!(schematic.dimensions, (3, 2, 3).try_into().unwrap());
+
+        let owned_schematic: Schematic = data.try_into().unwrap();
+        assert_eq!(owned_schematic, schematic);
+    }
+
+    #[test]
+    fn test_gzip_round_trip() {
+        use std::io::Write;
+
+        let schematic = fixture_3x3();
+
+        let mut compressor =
+            flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        compressor.write_all(DATA_3X3).unwrap();
+        let gzipped = compressor.finish().unwrap();
+
+        assert_eq!(Schematic::from_gzip(&gzipped).unwrap(), schematic);
+        assert_eq!(
+            Schematic::from_gzip(schematic.to_gzip()).unwrap(),
+            schematic
+        );
+        assert!(Schematic::from_gzip(DATA_3X3).is_err());
+    }
+
+    #[test]
+    fn test_from_bytes_with_progress() {
+        let data = DATA_3X3;
+        let mut fractions = Vec::new();
+
+        let schematic =
+            Schematic::from_bytes_with_progress(data, |fraction| fractions.push(fraction)).unwrap();
+
+        assert_eq!(schematic, Schematic::from_bytes(data).unwrap());
+        assert!(fractions.len() > 1);
+        assert!(fractions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(fractions.last(), Some(&1.0));
+    }
+
+    #[test]
+    fn test_from_bytes_verbose() {
+        let data = DATA_3X3;
+        Schematic::from_bytes_verbose(data).unwrap();
+
+        let mut data = Vec::from(data);
+        data[0..4].copy_from_slice(b"ABCD");
+
+        let description = Schematic::from_bytes_verbose(data).unwrap_err();
+
+        assert!(description.contains("MTSM"), "{description}");
+    }
+
     #[test]
     fn test_validate() {
         let mut schematic = Schematic::new((2, 2, 2).try_into().unwrap()).unwrap();
@@ -488,6 +2104,351 @@ mod tests {
         assert!(schematic.validate().is_ok());
     }
 
+    #[test]
+    fn test_air_id() {
+        let mut schematic = Schematic::new((2, 2, 2).try_into().unwrap()).unwrap();
+        assert_eq!(schematic.air_id(), Some(0));
+        assert_eq!(schematic.ignore_id, None);
+
+        schematic.register_content("default:dirt".into());
+        schematic.register_content("ignore".into());
+        assert_eq!(schematic.air_id(), Some(0));
+        assert_eq!(schematic.ignore_id, Some(2));
+
+        schematic.set_content_names(vec!["default:dirt".into(), "air".into()]);
+        assert_eq!(schematic.air_id(), Some(1));
+        assert_eq!(schematic.ignore_id, None);
+    }
+
+    #[rstest]
+    fn test_volume(#[from(fixture_3x3)] schematic: Schematic) {
+        assert_eq!(schematic.volume(), 18);
+        assert_eq!(schematic.volume(), schematic.num_nodes());
+
+        let schematic = schematic
+            .insert_layer(1, &Node::with_content_name("default:dirt".into()))
+            .unwrap();
+
+        assert_eq!(schematic.volume(), 27);
+        assert_eq!(schematic.volume(), schematic.num_nodes());
+    }
+
+    #[rstest]
+    fn test_content_entries(#[from(fixture_3x3)] schematic: Schematic) {
+        let entries: Vec<(u16, &str)> = schematic.content_entries().collect();
+
+        // The fixture doesn't list "air" first
+        assert_eq!(entries.first(), Some(&(0, "default:cobble")));
+        assert_eq!(entries[3], (3, "air"));
+        assert_eq!(entries.last(), Some(&(6, "default:pine_wood")));
+        assert_eq!(entries.len(), 7);
+    }
+
+    #[test]
+    fn test_name_grid_round_trip() {
+        let grid: Vec<Vec<Vec<String>>> = vec![
+            vec![
+                vec!["default:stone".into(), "default:dirt".into()],
+                vec!["air".into(), "default:torch".into()],
+            ],
+            vec![
+                vec!["default:stone".into(), "default:stone".into()],
+                vec!["air".into(), "air".into()],
+            ],
+            vec![
+                vec!["default:dirt".into(), "default:stone".into()],
+                vec!["air".into(), "default:mese".into()],
+            ],
+        ];
+
+        let schematic = Schematic::from_name_grid(&grid).unwrap();
+
+        assert_eq!(schematic.dimensions, MapVector::new(3, 2, 2).unwrap());
+        assert_eq!(
+            schematic
+                .node_at((0, 1, 1).try_into().unwrap())
+                .unwrap()
+                .content_name,
+            "default:torch"
+        );
+        assert_eq!(schematic.to_name_grid(), grid);
+    }
+
+    #[test]
+    fn test_from_name_grid_not_rectangular() {
+        let grid: Vec<Vec<Vec<String>>> = vec![
+            vec![vec!["air".into()], vec!["air".into()]],
+            vec![vec!["air".into()], vec!["air".into(), "air".into()]],
+        ];
+
+        assert!(matches!(
+            Schematic::from_name_grid(&grid),
+            Err(Error::IncorrectNodeCount {
+                found: 2,
+                expected: 1
+            })
+        ));
+        assert!(matches!(
+            Schematic::from_name_grid(&[]),
+            Err(Error::EmptyDimension)
+        ));
+    }
+
+    #[test]
+    fn test_fits_at_world_origin() {
+        let schematic = Schematic::new((10, 5, 1).try_into().unwrap()).unwrap();
+
+        assert!(schematic.fits_at_world_origin((0, 0, 0)));
+        assert!(schematic.fits_at_world_origin((30997, 31002, 31006)));
+        assert!(!schematic.fits_at_world_origin((30998, 0, 0)));
+        assert!(!schematic.fits_at_world_origin((0, 31003, 0)));
+        assert!(schematic.fits_at_world_origin((-31006, -31006, -31006)));
+        assert!(!schematic.fits_at_world_origin((-31007, 0, 0)));
+        assert!(!schematic.fits_at_world_origin((0, 0, i32::MIN)));
+        assert!(!schematic.fits_at_world_origin((i32::MAX, 0, 0)));
+    }
+
+    #[rstest]
+    #[case::with_air(vec!["default:stone", "air", "default:dirt"], 1)]
+    #[case::without_air(vec!["default:stone", "default:dirt"], 0)]
+    fn test_new_with_content_table(#[case] content_names: Vec<&str>, #[case] air_id: u16) {
+        let content_names: Vec<String> = content_names.into_iter().map(String::from).collect();
+
+        let schematic =
+            Schematic::new_with_content_table((2, 2, 2).try_into().unwrap(), content_names.clone())
+                .unwrap();
+
+        assert!(schematic.validate().is_ok());
+        assert_eq!(schematic.content_names().count(), 3);
+        assert_eq!(schematic.content_id_for_name("air"), Some(air_id));
+        for content_name in &content_names {
+            assert!(schematic.content_id_for_name(content_name).is_some());
+        }
+        assert!(schematic.nodes.iter().all(|node| node.content_id == air_id));
+        assert_eq!(schematic.content_id_for_name("default:dirt"), Some(2));
+    }
+
+    #[test]
+    fn test_new_with_duplicate_content_table() {
+        let result = Schematic::new_with_content_table(
+            (1, 1, 1).try_into().unwrap(),
+            vec!["default:dirt".to_string(), "default:dirt".to_string()],
+        );
+
+        assert!(matches!(result, Err(Error::DuplicateContentName(name)) if name == "default:dirt"));
+    }
+
+    #[test]
+    fn test_content_id_for_name_ci() {
+        let mut schematic = Schematic::new((1, 1, 1).try_into().unwrap()).unwrap();
+        let content_id = schematic.register_content("Default:Stone".into());
+
+        assert_eq!(schematic.content_id_for_name("default:stone"), None);
+        assert_eq!(
+            schematic.content_id_for_name_ci("default:stone"),
+            Some(content_id)
+        );
+        assert_eq!(
+            schematic.content_id_for_name_ci("DEFAULT:STONE"),
+            Some(content_id)
+        );
+        assert_eq!(schematic.content_id_for_name_ci("default:dirt"), None);
+    }
+
+    #[test]
+    fn test_is_empty_and_all_air() {
+        let mut schematic = Schematic::new((2, 2, 2).try_into().unwrap()).unwrap();
+        assert!(!schematic.is_empty());
+        assert!(schematic.is_all_air());
+
+        schematic
+            .place_node(
+                &Node::with_content_name("default:stone".into()),
+                (1, 1, 1).try_into().unwrap(),
+            )
+            .unwrap();
+        assert!(!schematic.is_all_air());
+
+        let empty_schematic = Schematic::with_array3(MapVector::ZERO, Array3::default((0, 0, 0)));
+        assert!(empty_schematic.is_empty());
+    }
+
+    #[rstest]
+    fn test_node_planes(#[from(fixture_3x3)] schematic: Schematic) {
+        assert_eq!(schematic.content_id_plane().count(), schematic.num_nodes());
+        assert_eq!(schematic.param1_plane().count(), schematic.num_nodes());
+        assert_eq!(schematic.param2_plane().count(), schematic.num_nodes());
+
+        let first_node = schematic.raw_node_at(MapVector::ZERO).unwrap();
+        assert_eq!(
+            schematic.content_id_plane().next(),
+            Some(first_node.content_id)
+        );
+        assert_eq!(
+            schematic.content_name_for_id(first_node.content_id),
+            Some("default:cobble")
+        );
+        assert_eq!(
+            schematic.param1_plane().next(),
+            Some(first_node.to_packed().1)
+        );
+    }
+
+    #[test]
+    fn test_from_occupancy() {
+        let mut grid = Array3::from_elem((2, 3, 4), false);
+        grid[(0, 0, 0)] = true;
+        grid[(1, 2, 3)] = true;
+        grid[(1, 0, 2)] = true;
+
+        let schematic = Schematic::from_occupancy(grid.view(), "default:stone".into()).unwrap();
+
+        assert_eq!(schematic.dimensions, MapVector::new(4, 3, 2).unwrap());
+        let stone_id = schematic.content_id_for_name("default:stone").unwrap();
+        let occupied = schematic.nodes.map(|node| node.content_id == stone_id);
+        assert_eq!(occupied, grid);
+        assert!(matches!(
+            Schematic::from_occupancy(Array3::from_elem((1, 0, 1), true).view(), "a".into()),
+            Err(Error::EmptyDimension)
+        ));
+    }
+
+    #[rstest]
+    fn test_likely_equal(#[from(fixture_3x3)] schematic: Schematic) {
+        let digest = schematic.digest();
+
+        let copy = schematic.clone();
+        assert_eq!(copy.digest(), digest);
+        assert!(copy.likely_equal(&schematic));
+
+        let mut changed = schematic.clone();
+        changed
+            .place_node(
+                &Node::with_content_name("default:mese".into()),
+                MapVector::ZERO,
+            )
+            .unwrap();
+        assert_ne!(changed.digest(), digest);
+        assert!(!changed.likely_equal(&schematic));
+
+        let other_dimensions = Schematic::new((3, 3, 1).try_into().unwrap()).unwrap();
+        let mut other_content = Schematic::new((3, 3, 1).try_into().unwrap()).unwrap();
+        other_content.register_content("default:stone".into());
+        assert_ne!(other_dimensions.digest(), digest);
+        assert_ne!(other_content.digest(), other_dimensions.digest());
+    }
+
+    #[rstest]
+    fn test_digest_invalidated_by_changes(#[from(fixture_3x3)] schematic: Schematic) {
+        let mut changed = schematic.clone();
+        let digest = changed.digest();
+
+        changed.raw_nodes_mut()[(0, 0, 0)].param2 += 1;
+        assert_ne!(changed.digest(), digest);
+        assert!(!changed.likely_equal(&schematic));
+
+        changed.raw_nodes_mut()[(0, 0, 0)].param2 -= 1;
+        assert_eq!(changed.digest(), digest);
+        assert!(changed.likely_equal(&schematic));
+
+        let mut recorder = changed.record_changes();
+        assert_eq!(recorder.digest(), digest);
+        recorder
+            .place_node(
+                &Node::with_content_name("default:mese".into()),
+                MapVector::ZERO,
+            )
+            .unwrap();
+        assert_ne!(recorder.digest(), digest);
+    }
+
+    #[test]
+    fn test_schematic_ref_dimensions() {
+        let schematic = Schematic::new((3, 1, 2).try_into().unwrap()).unwrap();
+
+        let rotated = schematic.rotate_left();
+
+        assert_eq!(rotated.dimensions(), MapVector::new(2, 1, 3).unwrap());
+        assert_eq!(
+            rotated.to_owned_schematic().dimensions,
+            rotated.dimensions()
+        );
+    }
+
+    #[rstest]
+    fn test_count_where(#[from(fixture_3x3)] mut schematic: Schematic) {
+        assert_eq!(
+            schematic.count_where(|_coordinates, node| node.param2 > 0),
+            0
+        );
+
+        for coordinates in [(0, 0, 0), (2, 1, 2)] {
+            let node = Node::new(
+                "default:pine_wood".into(),
+                SpawnProbability::Always,
+                true,
+                3,
+            );
+            schematic
+                .place_node(&node, coordinates.try_into().unwrap())
+                .unwrap();
+        }
+
+        assert_eq!(
+            schematic.count_where(|_coordinates, node| node.param2 > 0),
+            2
+        );
+        assert_eq!(
+            schematic.count_where(|coordinates, node| coordinates.y > 0 && node.param2 > 0),
+            1
+        );
+        assert_eq!(
+            schematic.count_where(|_coordinates, _node| true),
+            schematic.num_nodes()
+        );
+    }
+
+    #[test]
+    fn test_register_contents() {
+        let mut schematic = Schematic::new((1, 1, 1).try_into().unwrap()).unwrap();
+
+        let content_ids = schematic.register_contents(
+            ["default:dirt", "default:stone", "default:dirt", "air"].map(String::from),
+        );
+
+        assert_eq!(content_ids, &[1, 2, 1, 0]);
+        assert_eq!(
+            schematic.content_names,
+            &["air", "default:dirt", "default:stone"]
+        );
+    }
+
+    #[test]
+    fn test_name() {
+        let mut schematic = Schematic::new((2, 1, 2).try_into().unwrap()).unwrap();
+        assert_eq!(schematic.name(), None);
+
+        schematic.set_name(Some("Watchtower".to_string()));
+
+        let bigger_schematic = schematic
+            .insert_layer(0, &Node::with_content_name("default:dirt".into()))
+            .unwrap();
+        assert_eq!(bigger_schematic.name(), Some("Watchtower"));
+
+        let reparsed_schematic = Schematic::from_bytes(bigger_schematic.to_bytes()).unwrap();
+        assert_eq!(reparsed_schematic.name(), None);
+        assert_eq!(reparsed_schematic, bigger_schematic);
+    }
+
+    #[test]
+    fn test_reserve_content() {
+        let mut schematic = Schematic::new((1, 1, 1).try_into().unwrap()).unwrap();
+
+        schematic.reserve_content(1000);
+
+        assert!(schematic.content_names.capacity() >= 1001);
+    }
+
     #[test]
     fn test_convert_node_to_raw_node() {
         let mut schematic = Schematic::with_raw_nodes(
@@ -528,6 +2489,23 @@ mod tests {
         assert_eq!(node, found_node);
     }
 
+    #[test]
+    fn test_place_node_tracked() {
+        let mut schematic = Schematic::new((2, 2, 2).try_into().unwrap()).unwrap();
+        let node = Node::with_content_name("default:cobble".into());
+
+        assert!(
+            schematic
+                .place_node_tracked(&node, (0, 0, 0).try_into().unwrap())
+                .unwrap()
+        );
+        assert!(
+            !schematic
+                .place_node_tracked(&node, (1, 0, 0).try_into().unwrap())
+                .unwrap()
+        );
+    }
+
     #[test]
     fn test_place_node_out_of_bounds() {
         let mut schematic = Schematic::new((1, 1, 1).try_into().unwrap()).unwrap();
@@ -540,6 +2518,157 @@ mod tests {
         schematic.place_node(&node, coordinates).unwrap_err();
     }
 
+    #[rstest]
+    fn test_raw_node_at(schematic: Schematic) {
+        let coordinates = (2, 1, 0).try_into().unwrap();
+
+        let raw_node = schematic.raw_node_at(coordinates).unwrap();
+
+        assert_eq!(
+            schematic.content_name_for_id(raw_node.content_id()),
+            Some(
+                schematic
+                    .node_at(coordinates)
+                    .unwrap()
+                    .content_name
+                    .as_ref()
+            )
+        );
+        assert_eq!(schematic.raw_node_at((3, 0, 0).try_into().unwrap()), None);
+    }
+
+    #[test]
+    fn test_is_air() {
+        let mut schematic = Schematic::new((2, 2, 2).try_into().unwrap()).unwrap();
+        schematic
+            .place_node(
+                &Node::with_content_name("default:stone".into()),
+                (1, 0, 0).try_into().unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(schematic.is_air((0, 0, 0).try_into().unwrap()), Some(true));
+        assert_eq!(schematic.is_air((1, 0, 0).try_into().unwrap()), Some(false));
+        assert_eq!(schematic.is_air((2, 0, 0).try_into().unwrap()), None);
+    }
+
+    #[rstest]
+    fn test_raw_nodes(mut schematic: Schematic) {
+        let (z, y, x) = schematic.dimensions.as_shape();
+        assert_eq!(schematic.raw_nodes().shape(), &[z, y, x]);
+
+        schematic.raw_nodes_mut()[(0, 0, 2)] = RawNode::with_content_id(0);
+
+        assert_eq!(
+            schematic
+                .node_at((2, 0, 0).try_into().unwrap())
+                .unwrap()
+                .content_name,
+            "air"
+        );
+    }
+
+    #[test]
+    fn test_region() {
+        let mut schematic = Schematic::new((4, 4, 4).try_into().unwrap()).unwrap();
+        schematic
+            .place_node(
+                &Node::with_content_name("default:stone".into()),
+                (2, 1, 3).try_into().unwrap(),
+            )
+            .unwrap();
+
+        let region = schematic
+            .region((1, 0, 2).try_into().unwrap(), (2, 2, 2).try_into().unwrap())
+            .unwrap();
+
+        assert_eq!(region.shape(), &[2, 2, 2]);
+        assert_eq!(region[(1, 1, 1)].content_id, 1);
+        assert_eq!(region[(0, 0, 0)].content_id, 0);
+
+        schematic
+            .region_mut((1, 0, 2).try_into().unwrap(), (2, 2, 2).try_into().unwrap())
+            .unwrap()
+            .fill(RawNode::with_content_id(1));
+        assert_eq!(
+            schematic
+                .nodes
+                .iter()
+                .filter(|node| node.content_id == 1)
+                .count(),
+            8
+        );
+
+        schematic
+            .region((3, 0, 0).try_into().unwrap(), (2, 1, 1).try_into().unwrap())
+            .unwrap_err();
+    }
+
+    #[rstest]
+    fn test_neighbors(schematic: Schematic) {
+        let corner_neighbors = schematic
+            .neighbors((0, 0, 0).try_into().unwrap())
+            .collect::<Vec<(MapVector, Node)>>();
+        assert_eq!(corner_neighbors.len(), 3);
+        assert_eq!(
+            corner_neighbors[0],
+            (
+                (1, 0, 0).try_into().unwrap(),
+                Node::new("content:2".into(), SpawnProbability::Always, true, 0)
+            )
+        );
+
+        let mut schematic = Schematic::new((3, 3, 3).try_into().unwrap()).unwrap();
+        let node = Node::with_content_name("default:dirt".into());
+        schematic
+            .place_node(&node, (1, 2, 1).try_into().unwrap())
+            .unwrap();
+
+        let interior_neighbors = schematic
+            .neighbors((1, 1, 1).try_into().unwrap())
+            .collect::<Vec<(MapVector, Node)>>();
+        assert_eq!(interior_neighbors.len(), 6);
+        assert_eq!(interior_neighbors[3], ((1, 2, 1).try_into().unwrap(), node));
+        assert_eq!(interior_neighbors[0].1.content_name, "air");
+    }
+
+    #[rstest]
+    fn test_clear(mut schematic: Schematic) {
+        let num_content_names = schematic.content_names.len();
+
+        schematic.clear();
+
+        assert_eq!(schematic.dimensions, (3, 2, 3).try_into().unwrap());
+        assert_eq!(schematic.content_names.len(), num_content_names);
+        assert!(
+            schematic
+                .annotated_nodes()
+                .all(|annotated_node| annotated_node.node
+                    == Node::new("air".into(), SpawnProbability::Always, false, 0))
+        );
+    }
+
+    #[rstest]
+    fn test_normalize_placement(mut schematic: Schematic) {
+        schematic.layer_probabilities = vec![SpawnProbability::Custom(10), SpawnProbability::Never];
+        schematic.nodes[(0, 0, 0)].spawn_probability = SpawnProbability::Never.into();
+        schematic.nodes[(0, 0, 0)].force_placement = false;
+        schematic.nodes[(1, 1, 1)].spawn_probability = SpawnProbability::Custom(64).into();
+
+        schematic.normalize_placement();
+
+        assert!(
+            schematic
+                .layer_probabilities
+                .iter()
+                .all(|probability| *probability == SpawnProbability::Always)
+        );
+        assert!(schematic.annotated_nodes().all(|annotated_node| {
+            annotated_node.node.spawn_probability == SpawnProbability::Always
+                && annotated_node.node.force_placement
+        }));
+    }
+
     #[rstest]
     fn test_split_into_chunks(schematic: Schematic) {
         let chunks = schematic
@@ -550,6 +2679,34 @@ mod tests {
         assert!(chunks.iter().all(|chunk| chunk.nodes.len() == 6));
     }
 
+    #[test]
+    fn test_split_into_chunks_nonempty() {
+        let mut schematic = Schematic::new((4, 4, 4).try_into().unwrap()).unwrap();
+        // Only touches the chunk at the top right back corner, and the one below it
+        schematic
+            .fill(
+                (3, 1, 2).try_into().unwrap(),
+                (1, 2, 1).try_into().unwrap(),
+                &Node::with_content_name("default:stone".into()),
+            )
+            .unwrap();
+
+        let chunks: Vec<(MapVector, Schematic)> = schematic
+            .split_into_chunks_nonempty((2, 2, 2).try_into().unwrap())
+            .collect();
+
+        let offsets: Vec<MapVector> = chunks.iter().map(|(offset, _chunk)| *offset).collect();
+        assert_eq!(
+            offsets,
+            [
+                MapVector::new(2, 0, 2).unwrap(),
+                MapVector::new(2, 2, 2).unwrap()
+            ]
+        );
+        assert!(chunks.iter().all(|(_offset, chunk)| !chunk.is_all_air()));
+        assert!(!chunks[0].1.is_air((1, 1, 0).try_into().unwrap()).unwrap());
+    }
+
     #[rstest]
     fn test_rotate_left(schematic: Schematic) {
         // Sanity check
@@ -592,6 +2749,116 @@ mod tests {
         assert_eq!(iter.next().unwrap().content_id, 15);
     }
 
+    #[rstest]
+    fn test_rotated_layer_probabilities(mut schematic: Schematic) {
+        let layer_probabilities = vec![SpawnProbability::Custom(50), SpawnProbability::Never];
+        schematic
+            .layer_probabilities
+            .clone_from(&layer_probabilities);
+
+        for rotated_schematic in [
+            schematic.rotate_left(),
+            schematic.rotate_right(),
+            schematic.rotate_180(),
+        ] {
+            assert_eq!(rotated_schematic.layer_probabilities(), layer_probabilities);
+        }
+    }
+
+    #[test]
+    fn test_rotated_dimensions() {
+        let schematic = Schematic::new((3, 2, 1).try_into().unwrap()).unwrap();
+
+        assert_eq!(
+            schematic.rotate_left().dimensions(),
+            (1, 2, 3).try_into().unwrap()
+        );
+        assert_eq!(
+            schematic.rotate_right().dimensions(),
+            (1, 2, 3).try_into().unwrap()
+        );
+        assert_eq!(
+            schematic.rotate_180().dimensions(),
+            (3, 2, 1).try_into().unwrap()
+        );
+    }
+
+    #[rstest]
+    fn test_from_node_space(schematic: Schematic) {
+        let rotated = schematic.rotate_left();
+
+        let copied_schematic = Schematic::from_node_space(&rotated);
+
+        assert_eq!(
+            copied_schematic,
+            schematic.rotate_left().to_owned_schematic()
+        );
+        assert!(copied_schematic.nodes.is_standard_layout());
+        assert!(copied_schematic.validate().is_ok());
+    }
+
+    #[test]
+    fn test_into_rotated() {
+        let mut schematic = Schematic::with_raw_nodes(
+            (3, 2, 1).try_into().unwrap(),
+            (0..6)
+                .map(RawNode::with_content_id)
+                .collect::<Vec<RawNode>>(),
+        )
+        .unwrap();
+        (1..6).for_each(|i| {
+            schematic.register_content(format!("content:{i}").into());
+        });
+
+        for (rotated_ref, rotated) in [
+            (
+                schematic.rotate_left(),
+                schematic.clone().into_rotated_left(),
+            ),
+            (
+                schematic.rotate_right(),
+                schematic.clone().into_rotated_right(),
+            ),
+            (schematic.rotate_180(), schematic.clone().into_rotated_180()),
+        ] {
+            assert_eq!(rotated.dimensions.as_shape(), rotated_ref.nodes_view.dim());
+            assert_eq!(rotated.nodes, rotated_ref.nodes_view);
+            assert!(rotated.nodes.is_standard_layout());
+            assert!(rotated.validate().is_ok());
+        }
+
+        let mut rotated = schematic.into_rotated_left();
+        assert_eq!(rotated.dimensions, (1, 2, 3).try_into().unwrap());
+        // The X-axis of the original is now the Z-axis
+        assert_eq!(rotated.nodes[[2, 0, 0]].content_id, 2);
+
+        rotated
+            .fill(
+                (0, 1, 0).try_into().unwrap(),
+                (1, 1, 3).try_into().unwrap(),
+                &Node::with_content_name("default:glass".into()),
+            )
+            .unwrap();
+
+        let glass = rotated.content_id_for_name("default:glass").unwrap();
+        assert!(
+            rotated
+                .nodes
+                .slice(s![.., 1, ..])
+                .iter()
+                .all(|node| node.content_id == glass)
+        );
+        assert_eq!(rotated.nodes[[2, 0, 0]].content_id, 2);
+    }
+
+    /// The schematic in tests/3x3.mts, of 3x2x3 nodes.
+    const DATA_3X3: &[u8] = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/3x3.mts"));
+
+    #[fixture]
+    fn fixture_3x3() -> Schematic {
+        Schematic::from_bytes(DATA_3X3).unwrap()
+    }
+
     #[fixture]
     fn schematic() -> Schematic {
         let mut schematic = Schematic::with_raw_nodes(
@@ -614,6 +2881,7 @@ mod tests {
         fn assert_send<T: Send>() {}
         assert_send::<Schematic>();
         assert_send::<SchematicRef>();
+        assert_send::<AnnotatedNodeIterator>();
     }
 
     #[test]
@@ -0,0 +1,186 @@
+//! Converts a [Schematic] into a Wavefront OBJ mesh, e.g. to show a build in a 3D viewer outside
+//! of the game.
+//!
+//! Every node becomes a unit cube, colored through vertex colors (`v x y z r g b`), which most
+//! viewers and editors support without a separate material file.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::node::NodeSpace;
+
+use super::Schematic;
+
+/// Color of nodes whose content name isn't in the palette.
+const DEFAULT_COLOR: [f32; 3] = [0.5, 0.5, 0.5];
+
+/// A face of a unit cube: the direction of its neighbour in the `Schematic`, as `(x, y, z)`, and
+/// its corners, counter-clockwise when looking at the outside of the cube.
+///
+/// Luanti uses a left-handed coordinate system and OBJ a right-handed one, so the Z axis of the
+/// corners is flipped compared to the neighbours, or else the mesh would be mirrored.
+type Face = ((isize, isize, isize), [[u16; 3]; 4]);
+
+#[rustfmt::skip]
+const FACES: [Face; 6] = [
+    (( 1,  0,  0), [[1, 0, 0], [1, 1, 0], [1, 1, 1], [1, 0, 1]]),
+    ((-1,  0,  0), [[0, 0, 0], [0, 0, 1], [0, 1, 1], [0, 1, 0]]),
+    (( 0,  1,  0), [[0, 1, 0], [0, 1, 1], [1, 1, 1], [1, 1, 0]]),
+    (( 0, -1,  0), [[0, 0, 0], [1, 0, 0], [1, 0, 1], [0, 0, 1]]),
+    (( 0,  0, -1), [[0, 0, 1], [1, 0, 1], [1, 1, 1], [0, 1, 1]]),
+    (( 0,  0,  1), [[0, 0, 0], [0, 1, 0], [1, 1, 0], [1, 0, 0]]),
+];
+
+/// Renders the `schematic` as an OBJ mesh with a cube for every node that isn't air or "ignore",
+/// colored by looking up its content name in the `palette`.
+///
+/// With `cull_interior_faces`, faces between two neighbouring cubes are left out, as they can't
+/// be seen anyway.
+pub(super) fn to_obj(
+    schematic: &Schematic,
+    palette: &HashMap<String, [f32; 3]>,
+    cull_interior_faces: bool,
+) -> String {
+    let mut output = String::new();
+    let dimensions = schematic.dimensions;
+    writeln!(
+        output,
+        "# Luanti schematic of {}x{}x{} nodes",
+        dimensions.x, dimensions.y, dimensions.z
+    )
+    .unwrap();
+
+    let mut vertex_count = 0;
+    for ((z, y, x), raw_node) in schematic.nodes.indexed_iter() {
+        if !is_visible(schematic, (z, y, x)) {
+            continue;
+        }
+
+        let [r, g, b] = schematic
+            .content_name_for_id(raw_node.content_id)
+            .and_then(|content_name| palette.get(content_name))
+            .copied()
+            .unwrap_or(DEFAULT_COLOR);
+
+        for ((dx, dy, dz), corners) in FACES {
+            let neighbour = (
+                z.checked_add_signed(dz),
+                y.checked_add_signed(dy),
+                x.checked_add_signed(dx),
+            );
+            if cull_interior_faces
+                && let (Some(z), Some(y), Some(x)) = neighbour
+                && is_visible(schematic, (z, y, x))
+            {
+                continue;
+            }
+
+            for [corner_x, corner_y, corner_z] in corners {
+                // The cube of the node spans -(z + 1) to -z after flipping the Z axis
+                let vertex_x = x + usize::from(corner_x);
+                let vertex_y = y + usize::from(corner_y);
+                let vertex_z = usize::from(corner_z) as isize - (z as isize + 1);
+                writeln!(output, "v {vertex_x} {vertex_y} {vertex_z} {r} {g} {b}").unwrap();
+            }
+            writeln!(
+                output,
+                "f {} {} {} {}",
+                vertex_count + 1,
+                vertex_count + 2,
+                vertex_count + 3,
+                vertex_count + 4
+            )
+            .unwrap();
+            vertex_count += 4;
+        }
+    }
+
+    output
+}
+
+/// Whether the node at the `(z, y, x)` shape exists and gets a cube, i.e. isn't air or "ignore".
+fn is_visible(schematic: &Schematic, shape: (usize, usize, usize)) -> bool {
+    schematic.nodes.get(shape).is_some_and(|raw_node| {
+        Some(raw_node.content_id) != schematic.air_id
+            && Some(raw_node.content_id) != schematic.ignore_id
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::node::Node;
+    use crate::vector::MapVector;
+
+    fn count_lines(obj: &str, prefix: &str) -> usize {
+        obj.lines().filter(|line| line.starts_with(prefix)).count()
+    }
+
+    #[test]
+    fn test_to_obj_solid_cube() {
+        let mut schematic = Schematic::new(MapVector::splat(2).unwrap()).unwrap();
+        schematic
+            .fill(
+                MapVector::ZERO,
+                MapVector::splat(2).unwrap(),
+                &Node::with_content_name("default:stone".into()),
+            )
+            .unwrap();
+
+        let obj = to_obj(&schematic, &HashMap::new(), false);
+        assert_eq!(count_lines(&obj, "v "), 8 * 6 * 4);
+        assert_eq!(count_lines(&obj, "f "), 8 * 6);
+
+        // Only the 4 faces on each side of the big cube are left
+        let obj = to_obj(&schematic, &HashMap::new(), true);
+        assert_eq!(count_lines(&obj, "v "), 6 * 4 * 4);
+        assert_eq!(count_lines(&obj, "f "), 6 * 4);
+    }
+
+    #[test]
+    fn test_to_obj_culled_faces() {
+        let mut schematic = Schematic::new(MapVector::splat(3).unwrap()).unwrap();
+        schematic
+            .fill(
+                MapVector::ZERO,
+                MapVector::splat(3).unwrap(),
+                &Node::with_content_name("default:stone".into()),
+            )
+            .unwrap();
+
+        let all_faces = count_lines(&to_obj(&schematic, &HashMap::new(), false), "f ");
+        let culled_obj = to_obj(&schematic, &HashMap::new(), true);
+        let culled_faces = count_lines(&culled_obj, "f ");
+
+        assert_eq!(all_faces, 27 * 6);
+        // Only the 3x3 faces on each of the 6 sides of the big cube are left
+        assert_eq!(culled_faces, 6 * 3 * 3);
+        assert_eq!(count_lines(&culled_obj, "v "), culled_faces * 4);
+    }
+
+    #[test]
+    fn test_to_obj_colors() {
+        let mut schematic = Schematic::new((3, 1, 1).try_into().unwrap()).unwrap();
+        schematic
+            .place_node(
+                &Node::with_content_name("default:dirt".into()),
+                MapVector::ZERO,
+            )
+            .unwrap();
+        schematic
+            .place_node(
+                &Node::with_content_name("default:mese".into()),
+                (2, 0, 0).try_into().unwrap(),
+            )
+            .unwrap();
+        let palette = HashMap::from([("default:mese".to_string(), [1.0, 1.0, 0.0])]);
+
+        let obj = to_obj(&schematic, &palette, true);
+
+        assert_eq!(count_lines(&obj, "f "), 2 * 6);
+        assert_eq!(count_lines(&obj, "v 0 0 -1 0.5 0.5 0.5"), 3);
+        assert_eq!(count_lines(&obj, "v 3 1 0 1 1 0"), 3);
+        assert!(obj.ends_with("f 45 46 47 48\n"));
+    }
+}
@@ -4,9 +4,11 @@
 //! * <https://docs.luanti.org/for-creators/luanti-schematic-file-format/>
 //! * <https://github.com/luanti-org/luanti/blob/5.1.0/src/mapgen/mg_schematic.h>
 
-use flate2::read::ZlibDecoder;
+use flate2::Crc;
+use flate2::read::{GzDecoder, ZlibDecoder};
+use ndarray::Array3;
+use std::collections::HashSet;
 use std::io::Read;
-use std::iter::zip;
 use winnow::error::FromExternalError;
 
 use winnow::BStr;
@@ -26,65 +28,214 @@ use super::Schematic;
 
 pub(super) const MTS_MAGIC_BYTES: &[u8; 4] = b"MTSM";
 
+/// Size of a single node in the decompressed node data: the content ID (2 bytes), param1 and
+/// param2.
+pub(super) const BYTES_PER_NODE: usize = 4;
+
+/// Variations on how strict parsing is.
+#[derive(Debug, Copy, Clone)]
+struct ParseOptions {
+    /// Schematics with more nodes than this are refused with a [TooLarge](Error::TooLarge).
+    max_nodes: usize,
+    /// Whether to accept node data that isn't zlib compressed.
+    allow_uncompressed: bool,
+    /// Whether to calculate a CRC32 checksum of the decompressed node data.
+    checksum: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            max_nodes: usize::MAX,
+            allow_uncompressed: false,
+            checksum: false,
+        }
+    }
+}
+
 pub(super) fn parse(input: &[u8]) -> Result<Schematic, Error> {
+    parse_with_progress(input, |_fraction| {})
+}
+
+/// Like [parse()], but reports progress like [parse_into_with()] does.
+pub(super) fn parse_with_progress<F: FnMut(f32)>(
+    input: &[u8],
+    progress: F,
+) -> Result<Schematic, Error> {
+    parse_new(input, ParseOptions::default(), progress)
+}
+
+/// Like [parse()], but refuses to parse schematics with more than `max_nodes` nodes.
+pub(super) fn parse_limited(input: &[u8], max_nodes: usize) -> Result<Schematic, Error> {
+    let options = ParseOptions {
+        max_nodes,
+        ..Default::default()
+    };
+
+    parse_new(input, options, |_fraction| {})
+}
+
+/// Like [parse()], but when the node data doesn't start like zlib compressed data, it's read as
+/// is.
+pub(super) fn parse_allow_uncompressed(input: &[u8]) -> Result<Schematic, Error> {
+    let options = ParseOptions {
+        allow_uncompressed: true,
+        ..Default::default()
+    };
+
+    parse_new(input, options, |_fraction| {})
+}
+
+/// Like [parse()], but also returns the CRC32 checksum of the decompressed node data.
+pub(super) fn parse_with_checksum(input: &[u8]) -> Result<(Schematic, u32), Error> {
+    let options = ParseOptions {
+        checksum: true,
+        ..Default::default()
+    };
+
+    let empty_dimensions = MapVector { x: 0, y: 0, z: 0 };
+    let mut schematic = Schematic::with_array3(empty_dimensions, Array3::default((0, 0, 0)));
+    let checksum = parse_into_with(input, &mut schematic, options, |_fraction| {})?
+        .expect("a checksum to be calculated when asked for");
+
+    Ok((schematic, checksum))
+}
+
+fn parse_new<F: FnMut(f32)>(
+    input: &[u8],
+    options: ParseOptions,
+    progress: F,
+) -> Result<Schematic, Error> {
+    let empty_dimensions = MapVector { x: 0, y: 0, z: 0 };
+    let mut schematic = Schematic::with_array3(empty_dimensions, Array3::default((0, 0, 0)));
+    parse_into_with(input, &mut schematic, options, progress)?;
+
+    Ok(schematic)
+}
+
+/// Parses `input` into the given `schematic`, overwriting it. The `schematic`'s node array is
+/// reused when it has the same dimensions as the parsed schematic.
+pub(super) fn parse_into(input: &[u8], schematic: &mut Schematic) -> Result<(), Error> {
+    parse_into_with(input, schematic, ParseOptions::default(), |_fraction| {})?;
+
+    Ok(())
+}
+
+/// Like [parse_into()], but parses according to the given `options`. Too large schematics are
+/// refused before allocating anything for them. Also reports how far along parsing is to
+/// `progress`, as a fraction from 0.0 to 1.0, after each of the major stages.
+///
+/// Returns the CRC32 checksum of the decompressed node data when `options` asks for it.
+fn parse_into_with<F: FnMut(f32)>(
+    input: &[u8],
+    schematic: &mut Schematic,
+    options: ParseOptions,
+    mut progress: F,
+) -> Result<Option<u32>, Error> {
     let stream = &mut BStr::new(input);
 
     verify_magic_bytes(stream)?;
 
-    let _version = parse_version(stream)?;
+    let version = parse_version(stream)?;
     let dimensions = parse_dimensions(stream)?;
+    let volume = dimensions.volume();
+    if volume == 0 {
+        return Err(Error::EmptyDimension);
+    }
+    if volume > options.max_nodes {
+        return Err(Error::TooLarge {
+            volume,
+            max_nodes: options.max_nodes,
+        });
+    }
     let layer_probabilities: Vec<SpawnProbability> =
         parse_layer_probabilities(stream, dimensions.y)?;
     let name_ids = parse_name_ids(stream)?;
+    verify_unique_name_ids(&name_ids)?;
+    progress(0.1);
 
     // The rest of the data is zlib compressed
-    let decompressed = decompress(stream)?;
+    let max_size = volume * BYTES_PER_NODE;
+    let decompressed = if options.allow_uncompressed && !is_zlib_header(stream) {
+        stream[..stream.len().min(max_size)].to_vec()
+    } else {
+        decompress(stream, max_size)?
+    };
+    // Check the amount of node data before allocating the nodes, so a small file can't claim
+    // enormous dimensions to make it allocate a lot of memory
+    if decompressed.len() != max_size {
+        return Err(Error::IncorrectNodeCount {
+            found: decompressed.len() / BYTES_PER_NODE,
+            expected: volume,
+        });
+    }
+    let checksum = options.checksum.then(|| {
+        let mut crc = Crc::new();
+        crc.update(&decompressed);
+        crc.sum()
+    });
+    // Nothing can fail after the `schematic` starts being overwritten, so it's either fully
+    // replaced or left as it was
+    verify_content_ids(&decompressed[..volume * 2], name_ids.len())?;
     let node_stream = &mut BStr::new(&decompressed);
+    progress(0.5);
 
-    let num_nodes = dimensions.volume();
-    let raw_nodes = parse_nodes(node_stream, num_nodes, name_ids.len())?;
+    if schematic.nodes.dim() != dimensions.as_shape() {
+        schematic.nodes = Array3::default(dimensions.as_shape());
+    }
+    parse_nodes(node_stream, &mut schematic.nodes)?;
 
-    // TODO Come up with a better constructor that also takes the layer probabilities and content
-    // names
-    let mut schematic = Schematic::with_raw_nodes(dimensions, raw_nodes)?;
+    schematic.version = version;
+    schematic.dimensions = dimensions;
     schematic.layer_probabilities = layer_probabilities;
-    schematic.content_names = name_ids;
+    schematic.set_content_names(name_ids.into_iter().map(Into::into).collect());
+    schematic.name = None;
+    progress(1.0);
 
-    Ok(schematic)
+    Ok(checksum)
 }
 
-fn parse_nodes(
-    node_stream: &mut &BStr,
-    num_nodes: usize,
-    num_name_ids: usize,
-) -> Result<Vec<RawNode>, ContextError> {
-    let node_contents: Vec<u16> =
-        repeat(num_nodes, be_u16.verify(|v| (*v as usize) < num_name_ids))
-            .context(parser_expected("node contents to point to a valid name_id"))
-            .parse_next(node_stream)?;
+/// Checks that the content IDs at the start of the node data all point to one of the name IDs.
+///
+/// Returns an [InvalidContentIndex](Error::InvalidContentIndex) for the first one that doesn't,
+/// like [Schematic::validate()] does.
+fn verify_content_ids(content_id_data: &[u8], num_name_ids: usize) -> Result<(), Error> {
+    match content_id_data
+        .chunks_exact(2)
+        .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+        .find(|content_id| *content_id as usize >= num_name_ids)
+    {
+        Some(content_id) => Err(Error::InvalidContentIndex(content_id)),
+        None => Ok(()),
+    }
+}
 
-    let node_params1: Vec<(bool, u8)> = repeat(
-        num_nodes,
-        be_u8
-            .map(|v| ((v & 0x80) > 0, v & 0x7f))
-            .verify(|(_force_placement, probability)| is_valid_probability(*probability)),
-    )
-    .context(parser_expected("a probability value between 0-127, or 255"))
-    .parse_next(node_stream)?;
+/// Parses the node data into `nodes`, which should already have the dimensions of the schematic
+/// being parsed. The content IDs should already have been checked by [verify_content_ids()].
+fn parse_nodes(node_stream: &mut &BStr, nodes: &mut Array3<RawNode>) -> Result<(), ContextError> {
+    let mut node_content = be_u16.context(parser_expected("content IDs for nodes"));
+    for node in nodes.iter_mut() {
+        node.content_id = node_content.parse_next(node_stream)?;
+    }
 
-    let node_params2: Vec<u8> = repeat(num_nodes, be_u8)
-        .context(parser_expected("valid Param2 values for nodes"))
-        .parse_next(node_stream)?;
+    // The highest bit is whether to force placement, and the other 7 bits are the probability,
+    // so every byte is valid. Unlike the layer probabilities, "always" can't be the legacy 255
+    // here, as that means a force placed node that is always spawned.
+    let mut node_param1 = be_u8
+        .map(|v| ((v & 0x80) > 0, v & 0x7f))
+        .context(parser_expected("param1 values for nodes"));
+    for node in nodes.iter_mut() {
+        let (force_placement, spawn_probability) = node_param1.parse_next(node_stream)?;
+        node.force_placement = force_placement;
+        node.spawn_probability = SpawnProbability::from(spawn_probability).into();
+    }
 
-    let nodes: Vec<RawNode> = zip(node_contents, zip(node_params1, node_params2))
-        .map(
-            |(content, ((force_placement, spawn_probability), param2))| {
-                RawNode::new(content, spawn_probability.into(), force_placement, param2)
-            },
-        )
-        .collect();
+    let mut node_param2 = be_u8.context(parser_expected("valid Param2 values for nodes"));
+    for node in nodes.iter_mut() {
+        node.param2 = node_param2.parse_next(node_stream)?;
+    }
 
-    Ok(nodes)
+    Ok(())
 }
 
 fn verify_magic_bytes(stream: &mut &BStr) -> winnow::Result<()> {
@@ -139,23 +290,81 @@ fn parse_name_ids(stream: &mut &BStr) -> winnow::Result<Vec<String>> {
     .parse_next(stream)
 }
 
-fn decompress(stream: &mut &BStr) -> winnow::Result<Vec<u8>> {
+/// Content IDs are looked up by name, which only works when each name is listed once.
+pub(super) fn verify_unique_name_ids(name_ids: &[String]) -> Result<(), Error> {
+    let mut seen_names = HashSet::with_capacity(name_ids.len());
+
+    match name_ids
+        .iter()
+        .find(|name| !seen_names.insert(name.as_str()))
+    {
+        Some(duplicate_name) => Err(Error::DuplicateContentName(duplicate_name.clone())),
+        None => Ok(()),
+    }
+}
+
+/// Whether `stream` starts with a valid zlib header: a compression method of 8 ("deflate"), with a
+/// checksum that makes the first two bytes a multiple of 31.
+fn is_zlib_header(stream: &[u8]) -> bool {
+    match stream {
+        [cmf, flg, ..] => cmf & 0x0f == 8 && u16::from_be_bytes([*cmf, *flg]) % 31 == 0,
+        _ => false,
+    }
+}
+
+/// Decompresses the node data. Decompression stops after `max_size` bytes, so a small amount of
+/// compressed data can't blow up into an enormous amount of memory.
+fn decompress(stream: &mut &BStr, max_size: usize) -> Result<Vec<u8>, Error> {
     let compressed_size = stream.len();
-    let mut decompressor = ZlibDecoder::new(stream.as_ref());
+    let mut decompressor = ZlibDecoder::new(stream.as_ref()).take(max_size as u64);
 
     // The data will be at least this amount of bytes big. How big exactly is not known ahead of
     // time.
-    let mut decompressed = Vec::with_capacity(compressed_size);
+    let mut decompressed = Vec::with_capacity(compressed_size.min(max_size));
     decompressor
         .read_to_end(&mut decompressed)
-        .map_err(|err| ContextError::from_external_error(stream, err))?;
+        .map_err(|err| Error::DecompressionFailed(err.to_string()))?;
 
     Ok(decompressed)
 }
 
+/// Removes the gzip layer around `input` and parses the result like [parse()].
+///
+/// Decompression stops after `max_size` bytes, and when there's more than that the gzip file is
+/// refused with a [DecompressionFailed](Error::DecompressionFailed), so a small gzip file can't
+/// blow up into an enormous amount of memory.
+pub(super) fn parse_gzip(input: &[u8], max_size: usize) -> Result<Schematic, Error> {
+    let mut decompressed = Vec::new();
+    GzDecoder::new(input)
+        .take((max_size as u64).saturating_add(1))
+        .read_to_end(&mut decompressed)
+        .map_err(|err| Error::DecompressionFailed(err.to_string()))?;
+    if decompressed.len() > max_size {
+        return Err(Error::DecompressionFailed(format!(
+            "more than {max_size} bytes after removing gzip"
+        )));
+    }
+
+    parse(&decompressed)
+}
+
+/// Renders every `context()` description of the parse `error`, and its underlying cause, if any.
+pub(super) fn describe_error(error: &ContextError) -> String {
+    let mut descriptions: Vec<String> = error.context().map(ToString::to_string).collect();
+    if let Some(cause) = error.cause() {
+        descriptions.push(format!("caused by: {cause}"));
+    }
+
+    if descriptions.is_empty() {
+        "Parse error without further details".to_string()
+    } else {
+        format!("Parse error: {}", descriptions.join("; "))
+    }
+}
+
 /// To describe what was expected during parsing using `context()`, displayed when there are
 /// parsing errors.
-fn parser_expected(description: &'static str) -> StrContext {
+pub(crate) fn parser_expected(description: &'static str) -> StrContext {
     StrContext::Expected(StrContextValue::Description(description))
 }
 
@@ -167,7 +376,7 @@ fn is_valid_probability(value: u8) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use crate::node::NodeSpace;
+    use crate::node::{Node, NodeSpace};
 
     use super::*;
 
@@ -191,6 +400,281 @@ mod tests {
         assert_eq!(schematic.num_nodes(), 18);
     }
 
+    #[test]
+    fn test_parse_every_param1() {
+        let num_nodes = 256;
+        let mut node_data = vec![0; num_nodes * 2];
+        node_data.extend(0..=u8::MAX);
+        node_data.extend(vec![0; num_nodes]);
+        let mut nodes = Array3::default((1, 1, num_nodes));
+
+        parse_nodes(&mut BStr::new(&node_data), &mut nodes).unwrap();
+
+        for (param1, node) in (0..=u8::MAX).zip(&nodes) {
+            assert_eq!(node.force_placement, param1 & 0x80 > 0);
+            assert_eq!(node.to_packed().1, param1);
+        }
+        assert_eq!(nodes[(0, 0, 0x80)].spawn_probability, 0);
+        assert_eq!(nodes[(0, 0, 0xff)].spawn_probability, 127);
+    }
+
+    #[test]
+    fn test_parse_with_checksum() {
+        let data = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/3x3.mts"));
+
+        let (schematic, checksum) = parse_with_checksum(data).unwrap();
+        let (_schematic, checksum_again) = parse_with_checksum(data).unwrap();
+        assert_eq!(checksum, checksum_again);
+
+        // Compressing the same nodes differently doesn't change the checksum
+        let (_schematic, checksum_reserialized) =
+            parse_with_checksum(&schematic.to_bytes()).unwrap();
+        assert_eq!(checksum, checksum_reserialized);
+    }
+
+    #[test]
+    fn test_parse_into() {
+        let data = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/3x3.mts"));
+        let mut other_schematic = Schematic::new((2, 2, 2).try_into().unwrap()).unwrap();
+        other_schematic
+            .place_node(
+                &Node::with_content_name("default:dirt".into()),
+                (1, 0, 0).try_into().unwrap(),
+            )
+            .unwrap();
+        let other_data = other_schematic.to_bytes();
+
+        let mut schematic = Schematic::new((3, 2, 3).try_into().unwrap()).unwrap();
+        let nodes_allocation = schematic.nodes.as_ptr();
+
+        parse_into(data, &mut schematic).unwrap();
+        assert_eq!(schematic, parse(data).unwrap());
+        assert_eq!(
+            schematic.nodes.as_ptr(),
+            nodes_allocation,
+            "nodes of the same dimensions should have been parsed into the existing array"
+        );
+
+        parse_into(&other_data, &mut schematic).unwrap();
+        assert_eq!(schematic, other_schematic);
+    }
+
+    #[test]
+    fn test_failed_parse_into_leaves_schematic_intact() {
+        // Two nodes, of which the second refers to content that isn't listed
+        let mut invalid_schematic = Schematic::new((2, 1, 1).try_into().unwrap()).unwrap();
+        invalid_schematic.raw_nodes_mut()[(0, 0, 1)].content_id = 5;
+        let data = invalid_schematic.to_bytes();
+
+        let mut schematic = Schematic::new((3, 3, 3).try_into().unwrap()).unwrap();
+        schematic
+            .place_node(
+                &Node::with_content_name("default:dirt".into()),
+                (1, 1, 1).try_into().unwrap(),
+            )
+            .unwrap();
+        let original_schematic = schematic.clone();
+
+        let result = parse_into(&data, &mut schematic);
+
+        assert!(matches!(result, Err(Error::InvalidContentIndex(5))));
+        assert_eq!(schematic, original_schematic);
+        schematic.validate().unwrap();
+        assert_eq!(schematic.annotated_nodes().count(), 27);
+    }
+
+    #[test]
+    fn test_from_bytes_with_duplicate_content_name() {
+        let mut schematic = Schematic::new((2, 1, 1).try_into().unwrap()).unwrap();
+        schematic.register_content("default:dirt".into());
+        schematic.register_content("default:sand".into());
+        let mut data = schematic.to_bytes();
+        // Replace "default:sand" with "default:dirt", which has the same length
+        let position = data
+            .windows(12)
+            .position(|window| window == b"default:sand")
+            .unwrap();
+        data[position..position + 12].copy_from_slice(b"default:dirt");
+
+        let result = parse(&data);
+
+        assert!(matches!(result, Err(Error::DuplicateContentName(name)) if name == "default:dirt"));
+    }
+
+    #[test]
+    fn test_from_bytes_with_corrupt_node_data() {
+        let mut data = Schematic::new((1, 1, 1).try_into().unwrap())
+            .unwrap()
+            .to_bytes();
+        // Magic bytes, version, dimensions, a single layer probability and a content name
+        // count plus "air" make up the header
+        let header_size = 4 + 2 + 6 + 1 + 2 + (2 + 3);
+        data.truncate(header_size);
+        data.extend(b"certainly not zlib data");
+
+        let result = parse(&data);
+
+        assert!(matches!(result, Err(Error::DecompressionFailed(_))));
+    }
+
+    #[test]
+    fn test_parse_limited() {
+        let data = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/3x3.mts"));
+        assert!(parse_limited(data, 18).is_ok());
+        assert!(matches!(
+            parse_limited(data, 17),
+            Err(Error::TooLarge {
+                volume: 18,
+                max_nodes: 17
+            })
+        ));
+
+        // A header for an enormous schematic, without any actual data
+        let mut data = Vec::from(MTS_MAGIC_BYTES);
+        data.extend(4u16.to_be_bytes());
+        for _ in 0..3 {
+            data.extend(62000u16.to_be_bytes());
+        }
+
+        let result = parse_limited(&data, 1_000_000);
+
+        assert!(matches!(result, Err(Error::TooLarge { .. })));
+
+        // Within the limit, but with node data for fewer nodes than the header says
+        let data = air_cube_with_node_data(50, 2);
+
+        let result = parse_limited(&data, 125_000);
+
+        assert!(matches!(
+            result,
+            Err(Error::IncorrectNodeCount {
+                found: 2,
+                expected: 125_000
+            })
+        ));
+        assert!(parse_limited(&air_cube_with_node_data(50, 125_000), 125_000).is_ok());
+    }
+
+    #[test]
+    fn test_parse_gzip() {
+        let schematic = Schematic::new((2, 1, 1).try_into().unwrap()).unwrap();
+        let mut data = schematic.to_bytes();
+        assert_eq!(parse_gzip(&gzip(&data), usize::MAX).unwrap(), schematic);
+        assert_eq!(parse_gzip(&gzip(&data), data.len()).unwrap(), schematic);
+        assert!(matches!(
+            parse_gzip(&gzip(&data), data.len() - 1),
+            Err(Error::DecompressionFailed(_))
+        ));
+
+        // Far more data than the limit, which compresses really well
+        data.extend(vec![0; 1_000_000]);
+        assert!(matches!(
+            parse_gzip(&gzip(&data), 1000),
+            Err(Error::DecompressionFailed(_))
+        ));
+
+        // Data that isn't gzip at all
+        assert!(matches!(
+            parse_gzip(&schematic.to_bytes(), usize::MAX),
+            Err(Error::DecompressionFailed(_))
+        ));
+
+        // A header that ends early is left to the parser
+        assert!(matches!(
+            parse_gzip(&gzip(&data[..8]), usize::MAX),
+            Err(Error::ParseError(_))
+        ));
+    }
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut compressor = flate2::write::GzEncoder::new(Vec::new(), Default::default());
+        std::io::Write::write_all(&mut compressor, data).unwrap();
+
+        compressor.finish().unwrap()
+    }
+
+    #[test]
+    fn test_parse_empty_dimension() {
+        let mut data = Vec::from(MTS_MAGIC_BYTES);
+        data.extend(4u16.to_be_bytes());
+        for size in [0u16, 1, 1] {
+            data.extend(size.to_be_bytes());
+        }
+        data.push(127);
+        data.extend(0u16.to_be_bytes());
+        let compressor = flate2::write::ZlibEncoder::new(Vec::new(), Default::default());
+        data.extend(compressor.finish().unwrap());
+
+        let result = parse(&data);
+
+        assert!(matches!(result, Err(Error::EmptyDimension)));
+    }
+
+    /// Creates the bytes of a schematic of `size` along every axis with only "air" as content,
+    /// followed by zlib compressed node data for `num_nodes` air nodes.
+    fn air_cube_with_node_data(size: u16, num_nodes: usize) -> Vec<u8> {
+        let mut data = Vec::from(MTS_MAGIC_BYTES);
+        data.extend(4u16.to_be_bytes());
+        for _ in 0..3 {
+            data.extend(size.to_be_bytes());
+        }
+        data.extend(vec![127; size as usize]);
+        data.extend(1u16.to_be_bytes());
+        data.extend(3u16.to_be_bytes());
+        data.extend(b"air");
+        let mut compressor = flate2::write::ZlibEncoder::new(Vec::new(), Default::default());
+        std::io::Write::write_all(&mut compressor, &vec![0; num_nodes * BYTES_PER_NODE]).unwrap();
+        data.extend(compressor.finish().unwrap());
+
+        data
+    }
+
+    #[test]
+    fn test_parse_truncated_node_data() {
+        // A header for a large schematic, followed by node data for only a few nodes
+        let data = air_cube_with_node_data(1000, 3);
+
+        let result = parse(&data);
+
+        assert!(matches!(
+            result,
+            Err(Error::IncorrectNodeCount {
+                found: 3,
+                expected: 1_000_000_000
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_allow_uncompressed() {
+        let mut data = Schematic::new((2, 1, 1).try_into().unwrap())
+            .unwrap()
+            .to_bytes();
+        let header_size = 4 + 2 + 6 + 1 + 2 + (2 + 3);
+        data.truncate(header_size);
+        // Content IDs, param1 and param2 of both nodes
+        data.extend([0, 0, 0, 0, 0x7f, 0xff, 0, 3]);
+
+        assert!(parse(&data).is_err());
+
+        let schematic = parse_allow_uncompressed(&data).unwrap();
+
+        assert_eq!(schematic.nodes.len(), 2);
+        assert_eq!(
+            schematic.nodes[[0, 0, 1]],
+            RawNode::new(0, SpawnProbability::Always, true, 3)
+        );
+
+        // Regular, compressed data keeps working
+        let compressed = Schematic::new((2, 1, 1).try_into().unwrap())
+            .unwrap()
+            .to_bytes();
+        assert_eq!(
+            parse_allow_uncompressed(&compressed).unwrap(),
+            parse(&compressed).unwrap()
+        );
+    }
+
     #[test]
     fn test_from_bytes_with_invalid_data() {
         let data = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/3x3.mts"));
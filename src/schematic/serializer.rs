@@ -1,15 +1,16 @@
 use std::io::Write;
 
 use flate2::Compression;
-use flate2::write::ZlibEncoder;
+use flate2::write::{GzEncoder, ZlibEncoder};
 
 use crate::node::NodeSpace;
 
 use super::Schematic;
-use super::parser::MTS_MAGIC_BYTES;
+use super::parser::{BYTES_PER_NODE, MTS_MAGIC_BYTES};
 
-/// Converts the given [Schematic] into a byte format that Luanti can load.
-pub(super) fn to_bytes(schematic: &Schematic) -> Vec<u8> {
+/// Converts the given [Schematic] into a byte format that Luanti can load, with the node data
+/// compressed using the given zlib `compression` level.
+pub(super) fn to_bytes(schematic: &Schematic, compression: Compression) -> Vec<u8> {
     let mut output = Vec::new();
 
     output.extend(MTS_MAGIC_BYTES);
@@ -18,12 +19,8 @@ pub(super) fn to_bytes(schematic: &Schematic) -> Vec<u8> {
     output.extend(schematic.dimensions.y.to_be_bytes());
     output.extend(schematic.dimensions.z.to_be_bytes());
 
-    output.extend(
-        schematic
-            .layer_probabilities
-            .iter()
-            .map(|p| (u8::from(p)).to_be()),
-    );
+    // Single bytes, so there's no byte order to take care of
+    output.extend(schematic.layer_probabilities.iter().map(u8::from));
 
     output.extend((schematic.content_names.len() as u16).to_be_bytes());
     for content_name in &schematic.content_names {
@@ -32,24 +29,17 @@ pub(super) fn to_bytes(schematic: &Schematic) -> Vec<u8> {
     }
 
     // Node data is stored with zlib compression
-    let mut node_data: Vec<u8> = Vec::with_capacity(schematic.num_nodes() * 4);
-    node_data.extend(
-        schematic
-            .nodes
-            .iter()
-            .flat_map(|node| node.content_id.to_be_bytes()),
-    );
-
+    // Reserve exactly enough: the content ID (2 bytes), param1 and param2 of every node
+    let mut node_data: Vec<u8> = Vec::with_capacity(schematic.num_nodes() * BYTES_PER_NODE);
     node_data.extend(
         schematic
-            .nodes
-            .iter()
-            .map(|raw_node| u8::from(raw_node.force_placement) << 7 | raw_node.spawn_probability),
+            .content_id_plane()
+            .flat_map(|content_id| content_id.to_be_bytes()),
     );
+    node_data.extend(schematic.param1_plane());
+    node_data.extend(schematic.param2_plane());
 
-    node_data.extend(schematic.nodes.iter().map(|node| node.param2));
-
-    let mut compressor = ZlibEncoder::new(Vec::new(), Compression::default());
+    let mut compressor = ZlibEncoder::new(Vec::new(), compression);
     compressor
         .write_all(&node_data)
         .expect("node data should be compressed");
@@ -59,10 +49,21 @@ pub(super) fn to_bytes(schematic: &Schematic) -> Vec<u8> {
     output
 }
 
+/// Like [to_bytes()], but wraps the result in a gzip layer.
+pub(super) fn to_gzip(schematic: &Schematic) -> Vec<u8> {
+    let mut compressor = GzEncoder::new(Vec::new(), Compression::default());
+    compressor
+        .write_all(&to_bytes(schematic, Compression::default()))
+        .expect("schematic should be compressed");
+
+    compressor.finish().expect("gzip compressed data")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use crate::node::{Node, SpawnProbability};
     use crate::schematic::parser::parse;
 
     #[test]
@@ -70,7 +71,7 @@ mod tests {
         let original_data = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/3x3.mts"));
         let original_schematic = parse(original_data).unwrap();
 
-        let serialized_schematic = to_bytes(&original_schematic);
+        let serialized_schematic = to_bytes(&original_schematic, Compression::default());
         // The original data and serialized schematic don't always compare byte for byte because of
         // variation the zlib compression, so the best we can do here is re-parse the serialized
         // schematic and see if that comes out the same as the originally parsed schematic. The
@@ -79,4 +80,114 @@ mod tests {
 
         assert_eq!(original_schematic, reparsed_schematic);
     }
+
+    #[test]
+    fn test_content_names_keep_order() {
+        let original_data = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/3x3.mts"));
+        let original_schematic = parse(original_data).unwrap();
+
+        let reparsed_schematic =
+            parse(&to_bytes(&original_schematic, Compression::default())).unwrap();
+
+        assert_eq!(
+            reparsed_schematic.content_names,
+            original_schematic.content_names
+        );
+    }
+
+    #[test]
+    fn test_ignore_round_trip() {
+        let mut schematic = Schematic::new((3, 1, 1).try_into().unwrap()).unwrap();
+        schematic.register_content("default:stone".into());
+        let ignore = Node::with_content_name("ignore".into());
+        schematic
+            .place_node(&ignore, (0, 0, 0).try_into().unwrap())
+            .unwrap();
+        schematic
+            .place_node(&ignore, (2, 0, 0).try_into().unwrap())
+            .unwrap();
+        let parsed_schematic = parse(&to_bytes(&schematic, Compression::default())).unwrap();
+
+        let reparsed_schematic =
+            parse(&to_bytes(&parsed_schematic, Compression::default())).unwrap();
+
+        assert_eq!(reparsed_schematic.ignore_id, Some(2));
+        assert_eq!(
+            reparsed_schematic.content_id_for_name("ignore"),
+            reparsed_schematic.ignore_id
+        );
+        let content_ids: Vec<u16> = reparsed_schematic
+            .nodes
+            .iter()
+            .map(|node| node.content_id)
+            .collect();
+        assert_eq!(content_ids, [2, 0, 2]);
+        assert_eq!(reparsed_schematic, schematic);
+    }
+
+    #[test]
+    fn test_to_bytes_mostly_air() {
+        let mut schematic = Schematic::new((32, 32, 32).try_into().unwrap()).unwrap();
+        schematic
+            .place_node(
+                &Node::new("default:mese".into(), SpawnProbability::Custom(20), true, 5),
+                (31, 0, 17).try_into().unwrap(),
+            )
+            .unwrap();
+
+        let reparsed_schematic = parse(&to_bytes(&schematic, Compression::default())).unwrap();
+
+        assert_eq!(reparsed_schematic, schematic);
+    }
+
+    #[test]
+    fn test_layer_probabilities_round_trip() {
+        let mut schematic = Schematic::new((1, 5, 1).try_into().unwrap()).unwrap();
+        let layer_probabilities = [
+            SpawnProbability::Custom(1),
+            SpawnProbability::Custom(42),
+            SpawnProbability::Custom(126),
+            SpawnProbability::Never,
+            SpawnProbability::Always,
+        ];
+        for (y, probability) in layer_probabilities.iter().enumerate() {
+            schematic
+                .set_layer_probability(y as u16, *probability)
+                .unwrap();
+        }
+
+        let reparsed_schematic = parse(&to_bytes(&schematic, Compression::default())).unwrap();
+
+        assert_eq!(reparsed_schematic.layer_probabilities, layer_probabilities);
+    }
+
+    #[test]
+    fn test_layer_probabilities_mut_round_trip() {
+        let mut schematic = Schematic::new((2, 4, 2).try_into().unwrap()).unwrap();
+
+        schematic
+            .layer_probabilities_mut()
+            .fill(SpawnProbability::Custom(40));
+
+        let reparsed_schematic = parse(&to_bytes(&schematic, Compression::default())).unwrap();
+
+        assert_eq!(
+            reparsed_schematic.layer_probabilities(),
+            [SpawnProbability::Custom(40); 4]
+        );
+    }
+
+    #[test]
+    fn test_to_bytes_uncompressed() {
+        let original_data = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/3x3.mts"));
+        let original_schematic = parse(original_data).unwrap();
+
+        let serialized_schematic = original_schematic.to_bytes_uncompressed();
+
+        assert_eq!(
+            serialized_schematic,
+            original_schematic.clone().to_bytes_uncompressed()
+        );
+        assert_eq!(parse(&serialized_schematic).unwrap(), original_schematic);
+    }
 }
@@ -0,0 +1,320 @@
+//! Generators for basic shapes, as a starting point for building something bigger.
+
+use std::borrow::Cow;
+
+use crate::error::Error;
+use crate::node::{Node, RawNode};
+use crate::vector::MapVector;
+
+use super::font::{self, GLYPH_HEIGHT, GLYPH_WIDTH};
+use super::{Axis3, Schematic};
+
+pub(super) fn hollow_box(dimensions: MapVector, wall: Cow<'_, str>) -> Result<Schematic, Error> {
+    let mut schematic = Schematic::new(dimensions)?;
+    let wall = RawNode::with_content_id(schematic.register_content(wall));
+
+    let (size_z, size_y, size_x) = dimensions.as_shape();
+    for ((z, y, x), node) in schematic.nodes.indexed_iter_mut() {
+        let is_on_face =
+            x == 0 || y == 0 || z == 0 || x == size_x - 1 || y == size_y - 1 || z == size_z - 1;
+        if is_on_face {
+            *node = wall;
+        }
+    }
+
+    Ok(schematic)
+}
+
+pub(super) fn ellipsoid(radii: MapVector, material: Cow<'_, str>) -> Result<Schematic, Error> {
+    let diameter = |radius: u16| {
+        radius
+            .checked_mul(2)
+            .and_then(|double| double.checked_add(1))
+            .ok_or(Error::OutOfBounds)
+    };
+    let dimensions = MapVector::new(diameter(radii.x)?, diameter(radii.y)?, diameter(radii.z)?)?;
+
+    let mut schematic = Schematic::new(dimensions)?;
+    let material = RawNode::with_content_id(schematic.register_content(material));
+
+    // How far the coordinate is from the center, relative to the radius along that axis
+    let relative_distance = |coordinate: usize, radius: u16| {
+        let distance = coordinate as f64 - radius as f64;
+        if radius == 0 {
+            // Flat along this axis, so only the center counts as inside
+            if distance == 0.0 { 0.0 } else { f64::INFINITY }
+        } else {
+            distance / radius as f64
+        }
+    };
+
+    for ((z, y, x), node) in schematic.nodes.indexed_iter_mut() {
+        let distance_x = relative_distance(x, radii.x);
+        let distance_y = relative_distance(y, radii.y);
+        let distance_z = relative_distance(z, radii.z);
+
+        if distance_x.powi(2) + distance_y.powi(2) + distance_z.powi(2) <= 1.0 {
+            *node = material;
+        }
+    }
+
+    Ok(schematic)
+}
+
+pub(super) fn draw_line(
+    schematic: &mut Schematic,
+    from: MapVector,
+    to: MapVector,
+    node: &Node,
+) -> Result<(), Error> {
+    if schematic.raw_node_at(from).is_none() || schematic.raw_node_at(to).is_none() {
+        return Err(Error::OutOfBounds);
+    }
+    let raw_node = schematic.convert_node_to_raw_node(node);
+
+    // 3D version of Bresenham's line algorithm: always take a step along the axis with the
+    // biggest difference (the "driving" axis), and one along the other axes whenever their error
+    // says so.
+    let start = [from.x as i32, from.y as i32, from.z as i32];
+    let end = [to.x as i32, to.y as i32, to.z as i32];
+    let delta: [i32; 3] = std::array::from_fn(|axis| (end[axis] - start[axis]).abs());
+    let step: [i32; 3] = std::array::from_fn(|axis| (end[axis] - start[axis]).signum());
+    let driving_axis = (0..3)
+        .max_by_key(|axis| delta[*axis])
+        .expect("there to be three axes");
+
+    let mut errors: [i32; 3] = std::array::from_fn(|axis| 2 * delta[axis] - delta[driving_axis]);
+    let mut position = start;
+    for _ in 0..=delta[driving_axis] {
+        let [x, y, z] = position.map(|coordinate| coordinate as usize);
+        schematic.nodes[[z, y, x]] = raw_node;
+
+        for axis in 0..3 {
+            if axis == driving_axis {
+                continue;
+            }
+
+            if errors[axis] > 0 {
+                position[axis] += step[axis];
+                errors[axis] -= 2 * delta[driving_axis];
+            }
+            errors[axis] += 2 * delta[axis];
+        }
+        position[driving_axis] += step[driving_axis];
+    }
+
+    Ok(())
+}
+
+pub(super) fn draw_text(
+    schematic: &mut Schematic,
+    text: &str,
+    at: MapVector,
+    material: Cow<'_, str>,
+    plane: Axis3,
+) -> Result<(), Error> {
+    if schematic.raw_node_at(at).is_none() {
+        return Err(Error::OutOfBounds);
+    }
+    let material = RawNode::with_content_id(schematic.register_content(material));
+
+    for (index, character) in text.chars().enumerate() {
+        let glyph = font::glyph(character);
+        // Leave one column of space between the glyphs
+        let glyph_offset = index * (GLYPH_WIDTH + 1);
+
+        for row in 0..GLYPH_HEIGHT {
+            for column in 0..GLYPH_WIDTH {
+                if !font::is_set(&glyph, column, row) {
+                    continue;
+                }
+
+                let horizontal = glyph_offset + column;
+                // Rows go from the top to the bottom, while `at` is the bottom of the text
+                let vertical = GLYPH_HEIGHT - 1 - row;
+                let (x, y, z) = match plane {
+                    Axis3::X => (0, vertical, horizontal),
+                    Axis3::Y => (horizontal, 0, vertical),
+                    Axis3::Z => (horizontal, vertical, 0),
+                };
+                let shape = (at.z as usize + z, at.y as usize + y, at.x as usize + x);
+
+                // Parts of the text that fall outside the Schematic are left out
+                if let Some(node) = schematic.nodes.get_mut(shape) {
+                    *node = material;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::node::NodeSpace;
+
+    #[test]
+    fn test_draw_text() {
+        let mut schematic = Schematic::new((12, 7, 1).try_into().unwrap()).unwrap();
+
+        draw_text(
+            &mut schematic,
+            "HI",
+            (0, 0, 0).try_into().unwrap(),
+            "default:stone".into(),
+            Axis3::Z,
+        )
+        .unwrap();
+
+        let is_stone = |x, y| !schematic.is_air(MapVector::new(x, y, 0).unwrap()).unwrap();
+        // The bottom corners and the bar of the H
+        assert!(is_stone(0, 0));
+        assert!(is_stone(4, 0));
+        assert!(!is_stone(2, 0));
+        assert!(is_stone(2, 3));
+        // The top of the I, after a column of space
+        assert!(!is_stone(5, 6));
+        assert!(!is_stone(6, 6));
+        assert!(is_stone(7, 6));
+        assert!(is_stone(9, 6));
+        assert!(!is_stone(10, 6));
+        assert!(is_stone(8, 3));
+    }
+
+    #[test]
+    fn test_draw_text_clipped() {
+        let mut schematic = Schematic::new((1, 4, 3).try_into().unwrap()).unwrap();
+
+        draw_text(
+            &mut schematic,
+            "HI",
+            (0, 1, 1).try_into().unwrap(),
+            "default:stone".into(),
+            Axis3::X,
+        )
+        .unwrap();
+
+        // Only the left column of the H fits, which runs along Z for this plane
+        assert!(!schematic.is_air((0, 1, 1).try_into().unwrap()).unwrap());
+        assert!(!schematic.is_air((0, 3, 1).try_into().unwrap()).unwrap());
+        assert!(schematic.is_air((0, 1, 2).try_into().unwrap()).unwrap());
+
+        draw_text(
+            &mut schematic,
+            "HI",
+            (1, 0, 0).try_into().unwrap(),
+            "default:stone".into(),
+            Axis3::X,
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn test_hollow_box() {
+        let schematic = hollow_box((3, 3, 3).try_into().unwrap(), "default:glass".into()).unwrap();
+        let glass = schematic.content_id_for_name("default:glass").unwrap();
+
+        assert!(schematic.validate().is_ok());
+        assert_eq!(
+            schematic
+                .nodes
+                .iter()
+                .filter(|node| node.content_id == glass)
+                .count(),
+            26
+        );
+        assert_eq!(
+            schematic.nodes[[1, 1, 1]].content_id,
+            schematic.air_id.unwrap()
+        );
+
+        // Too thin to have an inside
+        let schematic = hollow_box((4, 2, 4).try_into().unwrap(), "default:glass".into()).unwrap();
+        assert!(schematic.nodes.iter().all(|node| node.content_id == glass));
+    }
+
+    #[test]
+    fn test_ellipsoid() {
+        let schematic = ellipsoid((2, 2, 2).try_into().unwrap(), "default:stone".into()).unwrap();
+        let stone = schematic.content_id_for_name("default:stone").unwrap();
+        let content_at = |x, y, z| schematic.nodes[[z, y, x]].content_id;
+
+        assert_eq!(schematic.dimensions, (5, 5, 5).try_into().unwrap());
+        assert!(schematic.validate().is_ok());
+        // Center and poles
+        for (x, y, z) in [(2, 2, 2), (2, 0, 2), (2, 4, 2), (0, 2, 2), (2, 2, 4)] {
+            assert_eq!(content_at(x, y, z), stone);
+        }
+        // Corners
+        for (x, y, z) in [(0, 0, 0), (4, 4, 4), (4, 0, 0), (0, 4, 4)] {
+            assert_eq!(content_at(x, y, z), schematic.air_id.unwrap());
+        }
+
+        // A flat disc
+        let schematic = ellipsoid((3, 0, 1).try_into().unwrap(), "default:stone".into()).unwrap();
+        assert_eq!(schematic.dimensions, (7, 1, 3).try_into().unwrap());
+        assert_eq!(schematic.nodes[[1, 0, 0]].content_id, stone);
+        assert_eq!(
+            schematic.nodes[[0, 0, 0]].content_id,
+            schematic.air_id.unwrap()
+        );
+    }
+
+    #[test]
+    fn test_draw_line() {
+        let mut schematic = Schematic::new((5, 5, 5).try_into().unwrap()).unwrap();
+        let node = Node::with_content_name("carts:rail".into());
+        let rail = schematic.register_content("carts:rail".into());
+
+        let drawn_coordinates = |schematic: &Schematic| {
+            let mut coordinates: Vec<(usize, usize, usize)> = schematic
+                .nodes
+                .indexed_iter()
+                .filter(|(_index, node)| node.content_id == rail)
+                .map(|((z, y, x), _node)| (x, y, z))
+                .collect();
+            coordinates.sort();
+
+            coordinates
+        };
+
+        draw_line(
+            &mut schematic,
+            (0, 0, 0).try_into().unwrap(),
+            (4, 4, 4).try_into().unwrap(),
+            &node,
+        )
+        .unwrap();
+        assert_eq!(
+            drawn_coordinates(&schematic),
+            &[(0, 0, 0), (1, 1, 1), (2, 2, 2), (3, 3, 3), (4, 4, 4)]
+        );
+
+        schematic.clear();
+        // Drawn backwards, to also cover negative steps
+        draw_line(
+            &mut schematic,
+            (4, 2, 4).try_into().unwrap(),
+            (0, 0, 4).try_into().unwrap(),
+            &node,
+        )
+        .unwrap();
+        assert_eq!(
+            drawn_coordinates(&schematic),
+            &[(0, 0, 4), (1, 1, 4), (2, 1, 4), (3, 2, 4), (4, 2, 4)]
+        );
+
+        assert!(matches!(
+            draw_line(
+                &mut schematic,
+                (0, 0, 0).try_into().unwrap(),
+                (0, 5, 0).try_into().unwrap(),
+                &node,
+            ),
+            Err(Error::OutOfBounds)
+        ));
+    }
+}
@@ -4,6 +4,12 @@ use crate::error::Error;
 /// schematics file format defines it as an unsigned 16-bit integer.
 const MAX_MAP_DIMENSION: u16 = 62013;
 
+/// The lowest and highest coordinate (inclusive) along any axis of a Luanti world.
+#[cfg(feature = "std")]
+pub(crate) const MIN_WORLD_COORDINATE: i32 = -31006;
+#[cfg(feature = "std")]
+pub(crate) const MAX_WORLD_COORDINATE: i32 = 31006;
+
 /// A map-aware, three-dimensional vector.
 ///
 /// "Map-aware" as it checks its values against the maximum map/schematic size of Luanti (see `MAX_MAP_DIMENSION`)
@@ -16,6 +22,9 @@ pub struct MapVector {
 }
 
 impl MapVector {
+    /// The origin of a schematic.
+    pub const ZERO: MapVector = MapVector { x: 0, y: 0, z: 0 };
+
     pub fn new(x: u16, y: u16, z: u16) -> Result<Self, Error> {
         if x >= MAX_MAP_DIMENSION || y >= MAX_MAP_DIMENSION || z >= MAX_MAP_DIMENSION {
             return Err(Error::OutOfBounds);
@@ -24,6 +33,12 @@ impl MapVector {
         Ok(MapVector { x, y, z })
     }
 
+    /// Creates a `MapVector` with the same value `v` along every axis, e.g. for the dimensions of
+    /// a cube.
+    pub fn splat(v: u16) -> Result<Self, Error> {
+        MapVector::new(v, v, v)
+    }
+
     pub fn volume(&self) -> usize {
         self.x as usize * self.y as usize * self.z as usize
     }
@@ -36,11 +51,40 @@ impl MapVector {
         MapVector::new(x, y, z).ok()
     }
 
+    /// Whether none of the coordinates of this `MapVector` exceed those of `other`. Unlike
+    /// comparing with `<=`, this checks every axis separately.
+    #[cfg(feature = "std")]
+    pub(crate) fn fits_within(&self, other: MapVector) -> bool {
+        self.x <= other.x && self.y <= other.y && self.z <= other.z
+    }
+
+    /// Returns the difference along each axis to get from this `MapVector` to `other`, as
+    /// `(x, y, z)`. Unlike the `MapVector`s themselves, these can be negative.
+    pub fn offset_to(&self, other: MapVector) -> (i32, i32, i32) {
+        (
+            i32::from(other.x) - i32::from(self.x),
+            i32::from(other.y) - i32::from(self.y),
+            i32::from(other.z) - i32::from(self.z),
+        )
+    }
+
     /// Converts the `MapVector` into a shape that can be used to access a row-major ndarray, such
     /// as a [Schematic](crate::schematic::Schematic)'s nodes.
     pub fn as_shape(self) -> (usize, usize, usize) {
         (self.z as usize, self.y as usize, self.x as usize)
     }
+
+    /// The inverse of [as_shape()](MapVector::as_shape): converts the `(z, y, x)` shape of an
+    /// ndarray back into a `MapVector`.
+    ///
+    /// Returns an [OutOfBounds](Error::OutOfBounds) when any of the sizes doesn't fit in a
+    /// `MapVector`.
+    pub fn from_shape(shape: (usize, usize, usize)) -> Result<Self, Error> {
+        let (z, y, x) = shape;
+        let to_u16 = |value: usize| u16::try_from(value).map_err(|_| Error::OutOfBounds);
+
+        MapVector::new(to_u16(x)?, to_u16(y)?, to_u16(z)?)
+    }
 }
 
 impl TryFrom<(u16, u16, u16)> for MapVector {
@@ -50,3 +94,53 @@ impl TryFrom<(u16, u16, u16)> for MapVector {
         MapVector::new(value.0, value.1, value.2)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shape_round_trip() {
+        let vector = MapVector::new(1, 2, 3).unwrap();
+
+        assert_eq!(vector.as_shape(), (3, 2, 1));
+        assert_eq!(MapVector::from_shape(vector.as_shape()).unwrap(), vector);
+    }
+
+    #[test]
+    fn test_zero_and_splat() {
+        assert_eq!(MapVector::ZERO, (0, 0, 0).try_into().unwrap());
+        assert_eq!(MapVector::splat(5).unwrap(), (5, 5, 5).try_into().unwrap());
+        assert!(matches!(
+            MapVector::splat(MAX_MAP_DIMENSION),
+            Err(Error::OutOfBounds)
+        ));
+    }
+
+    #[test]
+    fn test_offset_to() {
+        let from = MapVector::new(5, 5, 5).unwrap();
+
+        assert_eq!(
+            from.offset_to(MapVector::new(1, 2, 3).unwrap()),
+            (-4, -3, -2)
+        );
+        assert_eq!(
+            from.offset_to(MapVector::new(6, 5, 62012).unwrap()),
+            (1, 0, 62007)
+        );
+        assert_eq!(from.offset_to(from), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_from_shape_out_of_bounds() {
+        assert!(matches!(
+            MapVector::from_shape((1, 1, MAX_MAP_DIMENSION as usize)),
+            Err(Error::OutOfBounds)
+        ));
+        assert!(matches!(
+            MapVector::from_shape((usize::MAX, 1, 1)),
+            Err(Error::OutOfBounds)
+        ));
+    }
+}